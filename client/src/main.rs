@@ -6,7 +6,7 @@ use bevy_spacetimedb::{
 };
 use freecam::FreeCamPlugin;
 use module_bindings::{
-    DbConnection, PhysicsCollidersTableAccess, PhysicsRigidBodiesTableAccess,
+    ColliderType, DbConnection, PhysicsCollidersTableAccess, PhysicsRigidBodiesTableAccess,
     PhysicsWorldTableAccess, RigidBody,
 };
 use spacetimedb_sdk::DbContext;
@@ -114,9 +114,6 @@ fn on_rigid_body_inserted(
 
         let body = event.row.clone();
 
-        let pos = event.row.position.clone();
-        let rotation = event.row.rotation.clone();
-
         let collider = stdb
             .db()
             .physics_colliders()
@@ -124,22 +121,55 @@ fn on_rigid_body_inserted(
             .find(&body.collider_id)
             .unwrap();
         debug!("Collider: {:?}", collider);
-        let (mesh, material) = collider_to_mesh(&collider, &mut meshes, &mut materials);
+        let transform = to_transform(&body.position, &body.rotation);
+        let name = Name::from(format!("RigidBody#{}", event.row.id));
+        // The collider is offset from the body origin, keep it on a child entity so that the
+        // updates of the body transform do not lose it. The scale applies along the local axes of
+        // the collider, so round shapes are only approximated when it is not uniform
+        let collider_transform = to_transform(&body.collider_position, &body.collider_rotation)
+            .with_scale(Vec3::new(body.scale.x, body.scale.y, body.scale.z).abs());
+
         let entity = commands
-            .spawn((
-                Name::from(format!("RigidBody#{}", event.row.id)),
-                Transform::from_xyz(pos.x, pos.y, pos.z).with_rotation(Quat::from_xyzw(
-                    rotation.x, rotation.y, rotation.z, rotation.w,
-                )),
-                Mesh3d(mesh),
-                MeshMaterial3d(material.clone()),
-            ))
+            .spawn((name, transform, Visibility::default()))
+            .with_children(|parent| {
+                if collider.collider_type != ColliderType::Compound {
+                    let (mesh, material) = collider_to_mesh(&collider, &mut meshes, &mut materials);
+                    parent.spawn((collider_transform, Mesh3d(mesh), MeshMaterial3d(material)));
+                    return;
+                }
+
+                parent
+                    .spawn((collider_transform, Visibility::default()))
+                    .with_children(|parent| {
+                        for child in &collider.children {
+                            let Some(child_collider) =
+                                stdb.db().physics_colliders().id().find(&child.collider_id)
+                            else {
+                                continue;
+                            };
+
+                            let (mesh, material) =
+                                collider_to_mesh(&child_collider, &mut meshes, &mut materials);
+                            parent.spawn((
+                                to_transform(&child.position, &child.rotation),
+                                Mesh3d(mesh),
+                                MeshMaterial3d(material),
+                            ));
+                        }
+                    });
+            })
             .id();
 
         rigid_bodies.insert(event.row.id, entity);
     }
 }
 
+fn to_transform(position: &module_bindings::Vec3, rotation: &module_bindings::Quat) -> Transform {
+    Transform::from_xyz(position.x, position.y, position.z).with_rotation(Quat::from_xyzw(
+        rotation.x, rotation.y, rotation.z, rotation.w,
+    ))
+}
+
 fn on_rigid_body_updated(
     mut commands: Commands,
     mut events: ReadUpdateEvent<RigidBody>,
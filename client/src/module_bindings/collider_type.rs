@@ -7,6 +7,7 @@
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
 use super::collider_type_type::ColliderType;
+use super::compound_child_type::CompoundChild;
 use super::vec_3_type::Vec3;
 
 #[derive(__lib::ser::Serialize, __lib::de::Deserialize, Clone, PartialEq, Debug)]
@@ -21,6 +22,13 @@ pub struct Collider {
     pub point_a: Vec3,
     pub point_b: Vec3,
     pub point_c: Vec3,
+    pub points: Vec<Vec3>,
+    pub indices: Vec<u32>,
+    pub heights: Vec<f32>,
+    pub rows: u32,
+    pub columns: u32,
+    pub children: Vec<CompoundChild>,
+    pub border_radius: f32,
     pub collider_type: ColliderType,
 }
 
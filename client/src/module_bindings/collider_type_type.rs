@@ -23,6 +23,20 @@ pub enum ColliderType {
     Capsule,
 
     Triangle,
+
+    ConvexHull,
+
+    TriMesh,
+
+    Heightfield,
+
+    Compound,
+
+    RoundCuboid,
+
+    RoundCylinder,
+
+    RoundCone,
 }
 
 impl __sdk::InModule for ColliderType {
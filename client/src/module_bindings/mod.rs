@@ -8,10 +8,25 @@ use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
 pub mod collider_type;
 pub mod collider_type_type;
+pub mod collision_event_kind_type;
+pub mod collision_event_type;
+pub mod collision_groups_type;
+pub mod compound_child_type;
+pub mod computed_mass_properties_type;
+pub mod distance_joint_type;
+pub mod joint_motor_type;
+pub mod joint_type;
+pub mod joint_type_type;
+pub mod locked_axes_type;
+pub mod mat_3_type;
 pub mod physics_colliders_table;
+pub mod physics_collision_events_table;
+pub mod physics_distance_joints_table;
+pub mod physics_joints_table;
 pub mod physics_raycasts_table;
 pub mod physics_rigid_bodies_table;
 pub mod physics_rigid_body_properties_table;
+pub mod physics_shapecasts_table;
 pub mod physics_tick_world_reducer;
 pub mod physics_ticks_table;
 pub mod physics_triggers_table;
@@ -26,16 +41,33 @@ pub mod ray_cast_type;
 pub mod rigid_body_properties_type;
 pub mod rigid_body_type;
 pub mod rigid_body_type_type;
+pub mod shape_cast_hit_type;
+pub mod shape_cast_type;
 pub mod shoot_player_reducer;
 pub mod trigger_type;
 pub mod vec_3_type;
 
 pub use collider_type::Collider;
 pub use collider_type_type::ColliderType;
+pub use collision_event_kind_type::CollisionEventKind;
+pub use collision_event_type::CollisionEvent;
+pub use collision_groups_type::CollisionGroups;
+pub use compound_child_type::CompoundChild;
+pub use computed_mass_properties_type::ComputedMassProperties;
+pub use distance_joint_type::DistanceJoint;
+pub use joint_motor_type::JointMotor;
+pub use joint_type::Joint;
+pub use joint_type_type::JointType;
+pub use locked_axes_type::LockedAxes;
+pub use mat_3_type::Mat3;
 pub use physics_colliders_table::*;
+pub use physics_collision_events_table::*;
+pub use physics_distance_joints_table::*;
+pub use physics_joints_table::*;
 pub use physics_raycasts_table::*;
 pub use physics_rigid_bodies_table::*;
 pub use physics_rigid_body_properties_table::*;
+pub use physics_shapecasts_table::*;
 pub use physics_tick_world_reducer::{
     physics_tick_world, set_flags_for_physics_tick_world, PhysicsTickWorldCallbackId,
 };
@@ -52,6 +84,8 @@ pub use ray_cast_type::RayCast;
 pub use rigid_body_properties_type::RigidBodyProperties;
 pub use rigid_body_type::RigidBody;
 pub use rigid_body_type_type::RigidBodyType;
+pub use shape_cast_hit_type::ShapeCastHit;
+pub use shape_cast_type::ShapeCast;
 pub use shoot_player_reducer::{set_flags_for_shoot_player, shoot_player, ShootPlayerCallbackId};
 pub use trigger_type::Trigger;
 pub use vec_3_type::Vec3;
@@ -110,9 +144,13 @@ impl TryFrom<__ws::ReducerCallInfo<__ws::BsatnFormat>> for Reducer {
 #[doc(hidden)]
 pub struct DbUpdate {
     physics_colliders: __sdk::TableUpdate<Collider>,
+    physics_collision_events: __sdk::TableUpdate<CollisionEvent>,
+    physics_distance_joints: __sdk::TableUpdate<DistanceJoint>,
+    physics_joints: __sdk::TableUpdate<Joint>,
     physics_raycasts: __sdk::TableUpdate<RayCast>,
     physics_rigid_bodies: __sdk::TableUpdate<RigidBody>,
     physics_rigid_body_properties: __sdk::TableUpdate<RigidBodyProperties>,
+    physics_shapecasts: __sdk::TableUpdate<ShapeCast>,
     physics_ticks: __sdk::TableUpdate<PhysicsWorldTick>,
     physics_triggers: __sdk::TableUpdate<Trigger>,
     physics_world: __sdk::TableUpdate<PhysicsWorld>,
@@ -128,6 +166,15 @@ impl TryFrom<__ws::DatabaseUpdate<__ws::BsatnFormat>> for DbUpdate {
                 "physics_colliders" => db_update
                     .physics_colliders
                     .append(physics_colliders_table::parse_table_update(table_update)?),
+                "physics_collision_events" => db_update.physics_collision_events.append(
+                    physics_collision_events_table::parse_table_update(table_update)?,
+                ),
+                "physics_distance_joints" => db_update.physics_distance_joints.append(
+                    physics_distance_joints_table::parse_table_update(table_update)?,
+                ),
+                "physics_joints" => db_update
+                    .physics_joints
+                    .append(physics_joints_table::parse_table_update(table_update)?),
                 "physics_raycasts" => db_update
                     .physics_raycasts
                     .append(physics_raycasts_table::parse_table_update(table_update)?),
@@ -137,6 +184,9 @@ impl TryFrom<__ws::DatabaseUpdate<__ws::BsatnFormat>> for DbUpdate {
                 "physics_rigid_body_properties" => db_update.physics_rigid_body_properties.append(
                     physics_rigid_body_properties_table::parse_table_update(table_update)?,
                 ),
+                "physics_shapecasts" => db_update
+                    .physics_shapecasts
+                    .append(physics_shapecasts_table::parse_table_update(table_update)?),
                 "physics_ticks" => db_update
                     .physics_ticks
                     .append(physics_ticks_table::parse_table_update(table_update)?),
@@ -178,6 +228,21 @@ impl __sdk::DbUpdate for DbUpdate {
         diff.physics_colliders = cache
             .apply_diff_to_table::<Collider>("physics_colliders", &self.physics_colliders)
             .with_updates_by_pk(|row| &row.id);
+        diff.physics_collision_events = cache
+            .apply_diff_to_table::<CollisionEvent>(
+                "physics_collision_events",
+                &self.physics_collision_events,
+            )
+            .with_updates_by_pk(|row| &row.id);
+        diff.physics_distance_joints = cache
+            .apply_diff_to_table::<DistanceJoint>(
+                "physics_distance_joints",
+                &self.physics_distance_joints,
+            )
+            .with_updates_by_pk(|row| &row.id);
+        diff.physics_joints = cache
+            .apply_diff_to_table::<Joint>("physics_joints", &self.physics_joints)
+            .with_updates_by_pk(|row| &row.id);
         diff.physics_raycasts = cache
             .apply_diff_to_table::<RayCast>("physics_raycasts", &self.physics_raycasts)
             .with_updates_by_pk(|row| &row.id);
@@ -190,6 +255,9 @@ impl __sdk::DbUpdate for DbUpdate {
                 &self.physics_rigid_body_properties,
             )
             .with_updates_by_pk(|row| &row.id);
+        diff.physics_shapecasts = cache
+            .apply_diff_to_table::<ShapeCast>("physics_shapecasts", &self.physics_shapecasts)
+            .with_updates_by_pk(|row| &row.id);
         diff.physics_ticks = cache
             .apply_diff_to_table::<PhysicsWorldTick>("physics_ticks", &self.physics_ticks)
             .with_updates_by_pk(|row| &row.id);
@@ -212,9 +280,13 @@ impl __sdk::DbUpdate for DbUpdate {
 #[doc(hidden)]
 pub struct AppliedDiff<'r> {
     physics_colliders: __sdk::TableAppliedDiff<'r, Collider>,
+    physics_collision_events: __sdk::TableAppliedDiff<'r, CollisionEvent>,
+    physics_distance_joints: __sdk::TableAppliedDiff<'r, DistanceJoint>,
+    physics_joints: __sdk::TableAppliedDiff<'r, Joint>,
     physics_raycasts: __sdk::TableAppliedDiff<'r, RayCast>,
     physics_rigid_bodies: __sdk::TableAppliedDiff<'r, RigidBody>,
     physics_rigid_body_properties: __sdk::TableAppliedDiff<'r, RigidBodyProperties>,
+    physics_shapecasts: __sdk::TableAppliedDiff<'r, ShapeCast>,
     physics_ticks: __sdk::TableAppliedDiff<'r, PhysicsWorldTick>,
     physics_triggers: __sdk::TableAppliedDiff<'r, Trigger>,
     physics_world: __sdk::TableAppliedDiff<'r, PhysicsWorld>,
@@ -236,6 +308,21 @@ impl<'r> __sdk::AppliedDiff<'r> for AppliedDiff<'r> {
             &self.physics_colliders,
             event,
         );
+        callbacks.invoke_table_row_callbacks::<CollisionEvent>(
+            "physics_collision_events",
+            &self.physics_collision_events,
+            event,
+        );
+        callbacks.invoke_table_row_callbacks::<DistanceJoint>(
+            "physics_distance_joints",
+            &self.physics_distance_joints,
+            event,
+        );
+        callbacks.invoke_table_row_callbacks::<Joint>(
+            "physics_joints",
+            &self.physics_joints,
+            event,
+        );
         callbacks.invoke_table_row_callbacks::<RayCast>(
             "physics_raycasts",
             &self.physics_raycasts,
@@ -251,6 +338,11 @@ impl<'r> __sdk::AppliedDiff<'r> for AppliedDiff<'r> {
             &self.physics_rigid_body_properties,
             event,
         );
+        callbacks.invoke_table_row_callbacks::<ShapeCast>(
+            "physics_shapecasts",
+            &self.physics_shapecasts,
+            event,
+        );
         callbacks.invoke_table_row_callbacks::<PhysicsWorldTick>(
             "physics_ticks",
             &self.physics_ticks,
@@ -843,9 +935,13 @@ impl __sdk::SpacetimeModule for RemoteModule {
 
     fn register_tables(client_cache: &mut __sdk::ClientCache<Self>) {
         physics_colliders_table::register_table(client_cache);
+        physics_collision_events_table::register_table(client_cache);
+        physics_distance_joints_table::register_table(client_cache);
+        physics_joints_table::register_table(client_cache);
         physics_raycasts_table::register_table(client_cache);
         physics_rigid_bodies_table::register_table(client_cache);
         physics_rigid_body_properties_table::register_table(client_cache);
+        physics_shapecasts_table::register_table(client_cache);
         physics_ticks_table::register_table(client_cache);
         physics_triggers_table::register_table(client_cache);
         physics_world_table::register_table(client_cache);
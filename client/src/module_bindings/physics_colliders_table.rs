@@ -6,6 +6,7 @@
 #![allow(unused, clippy::all)]
 use super::collider_type::Collider;
 use super::collider_type_type::ColliderType;
+use super::compound_child_type::CompoundChild;
 use super::vec_3_type::Vec3;
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
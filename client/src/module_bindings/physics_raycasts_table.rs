@@ -4,6 +4,7 @@
 // This was generated using spacetimedb cli version 1.2.0 (commit ).
 
 #![allow(unused, clippy::all)]
+use super::collision_groups_type::CollisionGroups;
 use super::ray_cast_hit_type::RayCastHit;
 use super::ray_cast_type::RayCast;
 use super::vec_3_type::Vec3;
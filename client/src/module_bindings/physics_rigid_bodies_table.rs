@@ -4,6 +4,8 @@
 // This was generated using spacetimedb cli version 1.2.0 (commit ).
 
 #![allow(unused, clippy::all)]
+use super::collision_groups_type::CollisionGroups;
+use super::computed_mass_properties_type::ComputedMassProperties;
 use super::quat_type::Quat;
 use super::rigid_body_type::RigidBody;
 use super::rigid_body_type_type::RigidBodyType;
@@ -4,7 +4,9 @@
 // This was generated using spacetimedb cli version 1.2.0 (commit ).
 
 #![allow(unused, clippy::all)]
+use super::locked_axes_type::LockedAxes;
 use super::rigid_body_properties_type::RigidBodyProperties;
+use super::vec_3_type::Vec3;
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
 /// Table handle for the table `physics_rigid_body_properties`.
@@ -4,6 +4,7 @@
 // This was generated using spacetimedb cli version 1.2.0 (commit ).
 
 #![allow(unused, clippy::all)]
+use super::collision_groups_type::CollisionGroups;
 use super::quat_type::Quat;
 use super::trigger_type::Trigger;
 use super::vec_3_type::Vec3;
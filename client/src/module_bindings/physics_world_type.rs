@@ -21,6 +21,16 @@ pub struct PhysicsWorld {
     pub qvbh_dilation_factor: f32,
     pub length_unit: f32,
     pub normalized_prediction_distance: f32,
+    pub warm_start_coefficient: f32,
+    pub collision_events: bool,
+    pub sleeping_enabled: bool,
+    pub sleep_linear_threshold: f32,
+    pub sleep_angular_threshold: f32,
+    pub time_to_sleep: f32,
+    pub position_write_epsilon: f32,
+    pub rotation_write_epsilon: f32,
+    pub velocity_write_epsilon: f32,
+    pub step_count: u64,
     pub debug: bool,
     pub debug_time: bool,
     pub debug_triggers: bool,
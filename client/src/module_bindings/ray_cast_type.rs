@@ -6,6 +6,7 @@
 #![allow(unused, clippy::all)]
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
+use super::collision_groups_type::CollisionGroups;
 use super::ray_cast_hit_type::RayCastHit;
 use super::vec_3_type::Vec3;
 
@@ -18,6 +19,7 @@ pub struct RayCast {
     pub direction: Vec3,
     pub max_distance: f32,
     pub solid: bool,
+    pub collision_groups: CollisionGroups,
     pub hits: Vec<RayCastHit>,
     pub added_hits: Vec<RayCastHit>,
     pub removed_hits: Vec<RayCastHit>,
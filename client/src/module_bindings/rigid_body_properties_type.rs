@@ -6,6 +6,9 @@
 #![allow(unused, clippy::all)]
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
+use super::locked_axes_type::LockedAxes;
+use super::vec_3_type::Vec3;
+
 #[derive(__lib::ser::Serialize, __lib::de::Deserialize, Clone, PartialEq, Debug)]
 #[sats(crate = __lib)]
 pub struct RigidBodyProperties {
@@ -14,8 +17,12 @@ pub struct RigidBodyProperties {
     pub friction_static_coefficient: f32,
     pub friction_dynamic_coefficient: f32,
     pub restitution_coefficient: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub locked_axes: LockedAxes,
     pub mass: f32,
-    pub inv_mass: f32,
+    pub density: Option<f32>,
+    pub center_of_mass: Option<Vec3>,
 }
 
 impl __sdk::InModule for RigidBodyProperties {
@@ -6,6 +6,8 @@
 #![allow(unused, clippy::all)]
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
+use super::collision_groups_type::CollisionGroups;
+use super::computed_mass_properties_type::ComputedMassProperties;
 use super::quat_type::Quat;
 use super::rigid_body_type_type::RigidBodyType;
 use super::vec_3_type::Vec3;
@@ -24,6 +26,14 @@ pub struct RigidBody {
     pub body_type: RigidBodyType,
     pub collider_id: u64,
     pub properties_id: u64,
+    pub collider_position: Vec3,
+    pub collider_rotation: Quat,
+    pub scale: Vec3,
+    pub collision_groups: CollisionGroups,
+    pub ccd: bool,
+    pub sleeping: bool,
+    pub sleep_timer: f32,
+    pub mass_properties: ComputedMassProperties,
 }
 
 impl __sdk::InModule for RigidBody {
@@ -6,6 +6,7 @@
 #![allow(unused, clippy::all)]
 use spacetimedb_sdk::__codegen::{self as __sdk, __lib, __sats, __ws};
 
+use super::collision_groups_type::CollisionGroups;
 use super::quat_type::Quat;
 use super::vec_3_type::Vec3;
 
@@ -17,6 +18,8 @@ pub struct Trigger {
     pub position: Vec3,
     pub rotation: Quat,
     pub collider_id: u64,
+    pub scale: Vec3,
+    pub collision_groups: CollisionGroups,
     pub entities_inside: Vec<u64>,
     pub added_entities: Vec<u64>,
     pub removed_entities: Vec<u64>,
@@ -1,4 +1,6 @@
+use bevy::asset::RenderAssetUsages;
 use bevy::color::palettes::css::{GREEN_YELLOW, RED};
+use bevy::render::mesh::{Indices, PrimitiveTopology};
 use bevy::{color::palettes::css::GRAY, prelude::*};
 
 use crate::module_bindings::{Collider, ColliderType};
@@ -31,7 +33,8 @@ pub fn collider_to_mesh(
 
             (mesh, material)
         }
-        ColliderType::Cuboid => {
+        // Rounded shapes are drawn with sharp edges
+        ColliderType::Cuboid | ColliderType::RoundCuboid => {
             let material = materials.add(StandardMaterial {
                 base_color: GREEN_YELLOW.into(),
                 ..default()
@@ -54,7 +57,7 @@ pub fn collider_to_mesh(
 
             (mesh, material)
         }
-        ColliderType::Cylinder => {
+        ColliderType::Cylinder | ColliderType::RoundCylinder => {
             let material = materials.add(StandardMaterial {
                 base_color: RED.into(),
                 ..default()
@@ -76,7 +79,7 @@ pub fn collider_to_mesh(
 
             (mesh, material)
         }
-        ColliderType::Cone => {
+        ColliderType::Cone | ColliderType::RoundCone => {
             let material = materials.add(StandardMaterial {
                 base_color: RED.into(),
                 ..default()
@@ -85,5 +88,105 @@ pub fn collider_to_mesh(
 
             (mesh, material)
         }
+        ColliderType::ConvexHull => {
+            let material = materials.add(StandardMaterial {
+                base_color: RED.into(),
+                ..default()
+            });
+            // The hull faces are not replicated, draw the bounding box of its vertices instead
+            let points: Vec<Vec3> = collider
+                .points
+                .iter()
+                .map(|point| Vec3::new(point.x, point.y, point.z))
+                .collect();
+            let min = points.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+            let max = points.iter().copied().fold(Vec3::NEG_INFINITY, Vec3::max);
+            let mesh = if points.is_empty() {
+                meshes.add(Sphere::new(0.1))
+            } else {
+                let size = max - min;
+                meshes.add(
+                    Mesh::from(Cuboid::new(size.x, size.y, size.z))
+                        .translated_by((min + max) / 2.0),
+                )
+            };
+
+            (mesh, material)
+        }
+        ColliderType::TriMesh => {
+            let material = materials.add(StandardMaterial {
+                base_color: GRAY.into(),
+                ..default()
+            });
+            let positions = collider
+                .points
+                .iter()
+                .map(|point| [point.x, point.y, point.z])
+                .collect();
+            let mesh = meshes.add(triangle_mesh(positions, collider.indices.clone()));
+
+            (mesh, material)
+        }
+        ColliderType::Heightfield => {
+            let material = materials.add(StandardMaterial {
+                base_color: GRAY.into(),
+                ..default()
+            });
+            let mesh = meshes.add(heightfield_mesh(collider));
+
+            (mesh, material)
+        }
+        ColliderType::Compound => {
+            // The children are spawned as separate entities, see `on_rigid_body_inserted`
+            let material = materials.add(StandardMaterial::default());
+            let mesh = meshes.add(triangle_mesh(Vec::new(), Vec::new()));
+
+            (mesh, material)
+        }
+    }
+}
+
+fn triangle_mesh(positions: Vec<[f32; 3]>, indices: Vec<u32>) -> Mesh {
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_indices(Indices::U32(indices))
+    .with_computed_normals()
+}
+
+/// Builds the same grid as the server: centered on the body, rows along Z, columns along X,
+/// and scaled by the collider size.
+fn heightfield_mesh(collider: &Collider) -> Mesh {
+    let (rows, columns) = (collider.rows, collider.columns);
+    if rows < 2 || columns < 2 || collider.heights.len() != (rows * columns) as usize {
+        warn!("Invalid heightfield collider: {:?}", collider.id);
+        return Mesh::from(Sphere::new(0.1));
+    }
+
+    let scale = collider.size;
+    let mut positions = Vec::with_capacity(collider.heights.len());
+    for row in 0..rows {
+        for column in 0..columns {
+            let height = collider.heights[(row * columns + column) as usize];
+            positions.push([
+                (column as f32 / (columns - 1) as f32 - 0.5) * scale.x,
+                height * scale.y,
+                (row as f32 / (rows - 1) as f32 - 0.5) * scale.z,
+            ]);
+        }
     }
+
+    let mut indices = Vec::with_capacity(((rows - 1) * (columns - 1) * 6) as usize);
+    for row in 0..rows - 1 {
+        for column in 0..columns - 1 {
+            let top_left = row * columns + column;
+            let bottom_left = top_left + columns;
+            indices.extend([top_left, bottom_left, top_left + 1]);
+            indices.extend([bottom_left, bottom_left + 1, top_left + 1]);
+        }
+    }
+
+    triangle_mesh(positions, indices)
 }
@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+use spacetimedb::SpacetimeType;
+
+/// Bitmasks filtering which rigid bodies, triggers and raycasts can interact with each other,
+/// similar to parry's `InteractionGroups`.
+///
+/// Two objects interact only if each one is a member of at least one group allowed by the
+/// filter of the other, e.g. players in group 1 with a filter excluding group 1 never collide
+/// with each other while still colliding with everything else.
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollisionGroups {
+    /// The groups this object is part of, one bit per group.
+    pub memberships: u32,
+    /// The groups this object can interact with, one bit per group.
+    pub filter: u32,
+}
+
+impl CollisionGroups {
+    /// Member of every group and interacting with every group, the default.
+    pub const ALL: Self = Self::new(u32::MAX, u32::MAX);
+
+    /// Member of no group, never interacts with anything.
+    pub const NONE: Self = Self::new(0, 0);
+
+    pub const fn new(memberships: u32, filter: u32) -> Self {
+        Self {
+            memberships,
+            filter,
+        }
+    }
+
+    pub const fn with_memberships(mut self, memberships: u32) -> Self {
+        self.memberships = memberships;
+        self
+    }
+
+    pub const fn with_filter(mut self, filter: u32) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Returns true if both objects accept each other's memberships.
+    pub const fn interacts_with(&self, other: &Self) -> bool {
+        (self.memberships & other.filter) != 0 && (other.memberships & self.filter) != 0
+    }
+}
+
+impl Default for CollisionGroups {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl Display for CollisionGroups {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CollisionGroups(memberships: {:#034b}, filter: {:#034b})",
+            self.memberships, self.filter
+        )
+    }
+}
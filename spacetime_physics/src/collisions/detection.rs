@@ -1,14 +1,16 @@
-use crate::RigidBodyData;
+use parry3d::query::{ContactManifoldsWorkspace, TrackedContact};
 
-use super::CollisionPoint;
+use crate::{math::Vec3, RigidBodyData};
+
+use super::{CollisionPoint, ContactData, ContactManifold};
 
 pub fn test_collision(
     body_a: &RigidBodyData,
     body_b: &RigidBodyData,
     prediction: f32,
 ) -> Option<CollisionPoint> {
-    let iso_a = body_a.into();
-    let iso_b = body_b.into();
+    let iso_a = body_a.collider_isometry();
+    let iso_b = body_b.collider_isometry();
 
     let result = body_a
         .shape()
@@ -35,3 +37,58 @@ pub fn test_collision(
         }
     })
 }
+
+/// Updates the contact manifolds between two bodies, contacts that are still present keep the
+/// [`ContactData`] they had during the previous call.
+pub fn update_contact_manifolds(
+    body_a: &RigidBodyData,
+    body_b: &RigidBodyData,
+    prediction: f32,
+    manifolds: &mut Vec<ContactManifold>,
+    workspace: &mut Option<ContactManifoldsWorkspace>,
+) {
+    body_a.shape().contact_manifolds(
+        &body_a.collider_isometry(),
+        body_b.shape(),
+        &body_b.collider_isometry(),
+        prediction,
+        manifolds,
+        workspace,
+    );
+}
+
+/// Converts a point of a contact manifold between two bodies to a [`CollisionPoint`].
+pub fn manifold_collision_point(
+    body_a: &RigidBodyData,
+    body_b: &RigidBodyData,
+    manifold: &ContactManifold,
+    contact: &TrackedContact<ContactData>,
+) -> CollisionPoint {
+    // Points are expressed in the local space of the colliders, or of the sub-shape for
+    // composite shapes, bring them back to the local space of the bodies
+    let collider_a = body_a.local_collider_isometry();
+    let collider_b = body_b.local_collider_isometry();
+    let (local_a, local_normal): (Vec3, Vec3) = match manifold.subshape_pos1 {
+        Some(pos) => (
+            (collider_a * pos * contact.local_p1).into(),
+            (collider_a * pos * manifold.local_n1).into(),
+        ),
+        None => (
+            (collider_a * contact.local_p1).into(),
+            (collider_a * manifold.local_n1).into(),
+        ),
+    };
+    let local_b: Vec3 = match manifold.subshape_pos2 {
+        Some(pos) => (collider_b * pos * contact.local_p2).into(),
+        None => (collider_b * contact.local_p2).into(),
+    };
+
+    CollisionPoint {
+        world_a: body_a.world_point(local_a),
+        world_b: body_b.world_point(local_b),
+        local_a,
+        local_b,
+        normal: body_a.rotation().rotate(local_normal),
+        distance: contact.dist,
+    }
+}
@@ -1,5 +1,17 @@
 use crate::math::Vec3;
 
+/// A contact manifold between two bodies, generated and kept up to date by parry.
+pub type ContactManifold = parry3d::query::ContactManifold<(), ContactData>;
+
+/// Solver data attached to each point of a [`ContactManifold`]. Parry carries it over when
+/// the contact points are matched with the ones of the previous frame, which lets the solver
+/// warm start from the multipliers accumulated during the previous substep.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ContactData {
+    /// The normal Lagrange multiplier accumulated during the last substep.
+    pub normal_lagrange: f32,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CollisionPoint {
     /// Position of the contact on the first object in world space.
@@ -1,7 +1,9 @@
+mod collision_groups;
 mod detection;
 mod manifold;
 mod shape_wrapper;
 
+pub use collision_groups::*;
 pub use detection::*;
 pub use manifold::*;
 pub use shape_wrapper::*;
@@ -1,9 +1,24 @@
-use crate::{tables::Collider, ColliderType};
+use std::collections::HashMap;
+
+use log::warn;
+
+use crate::{
+    math::Vec3, tables::Collider, ColliderId, ColliderType, ComputedMassProperties, ContactManifold,
+};
 use parry3d::{
     bounding_volume::{Aabb, BoundingVolume},
-    na::Isometry3,
-    query::{contact, intersection_test, Contact, Ray, RayCast, RayIntersection},
-    shape::{Ball, Capsule, Cone, Cuboid, Cylinder, HalfSpace, Shape, Triangle},
+    mass_properties::MassProperties,
+    math::Point,
+    na::{DMatrix, Isometry3, Vector3},
+    query::{
+        cast_shapes, contact, distance, intersection_test, Contact, ContactManifoldsWorkspace,
+        DefaultQueryDispatcher, PersistentQueryDispatcher, PointProjection, Ray, RayCast,
+        RayIntersection, ShapeCastHit, ShapeCastOptions,
+    },
+    shape::{
+        Ball, Capsule, Compound, Cone, ConvexPolyhedron, Cuboid, Cylinder, HalfSpace, HeightField,
+        RoundCone, RoundCuboid, RoundCylinder, RoundShape, Shape, SharedShape, TriMesh, Triangle,
+    },
 };
 
 /// Acts as a wrapper around spacetime_physics colliders and Parry's shapes,
@@ -16,9 +31,92 @@ pub enum ShapeWrapper {
     Cylinder(Cylinder),
     Cone(Cone),
     Triangle(Triangle),
+    ConvexHull(ConvexPolyhedron),
+    TriMesh(TriMesh),
+    Heightfield(HeightField),
+    Compound(Compound),
+    RoundCuboid(RoundCuboid),
+    RoundCylinder(RoundCylinder),
+    RoundCone(RoundCone),
 }
 
 impl ShapeWrapper {
+    /// Creates the shape of a collider scaled along its local axes, resolving the children of
+    /// compound colliders. See [`Collider::scaled`] for how each shape is scaled.
+    pub fn new(
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+        scale: Vec3,
+    ) -> Self {
+        let scaled;
+        let collider = if scale == Vec3::ONE {
+            collider
+        } else {
+            scaled = collider.scaled(scale);
+            &scaled
+        };
+
+        if collider.collider_type != ColliderType::Compound {
+            return ShapeWrapper::from(collider);
+        }
+
+        let shapes: Vec<_> = collider
+            .children
+            .iter()
+            .filter_map(|child| {
+                let Some(child_collider) = colliders.get(&child.collider_id) else {
+                    warn!(
+                        "Collider {} references the missing child collider {}, ignoring it",
+                        collider.id, child.collider_id
+                    );
+                    return None;
+                };
+
+                if !child_collider.is_compound_child_supported() {
+                    warn!(
+                        "Collider {} can't have a {:?} child (collider {}), ignoring it",
+                        collider.id, child_collider.collider_type, child.collider_id
+                    );
+                    return None;
+                }
+
+                let isometry = Isometry3::from_parts(child.position.into(), child.rotation.into());
+                let shape = if scale == Vec3::ONE {
+                    ShapeWrapper::from(child_collider)
+                } else {
+                    ShapeWrapper::from(&child_collider.scaled(scale))
+                };
+                Some((isometry, shape.into_shared_shape()))
+            })
+            .collect();
+
+        if shapes.is_empty() {
+            warn!(
+                "Collider {} is a compound without valid children, falling back to a point",
+                collider.id
+            );
+            return ShapeWrapper::Sphere(Ball::new(0.0));
+        }
+
+        ShapeWrapper::Compound(Compound::new(shapes))
+    }
+
+    /// Returns the mass properties of the shape for a uniform density, in the collider's local
+    /// space. Shapes without volume (planes, triangles, heightfields) have no mass and triangle
+    /// meshes are considered closed.
+    pub fn mass_properties(&self, density: f32) -> ComputedMassProperties {
+        let properties: MassProperties = self.as_parry_shape().mass_properties(density);
+        if properties.mass() <= f32::EPSILON {
+            return ComputedMassProperties::default();
+        }
+
+        ComputedMassProperties {
+            mass: properties.mass(),
+            center_of_mass: properties.local_com.into(),
+            inertia_tensor: properties.reconstruct_inertia_matrix().into(),
+        }
+    }
+
     pub fn collision_aabb(&self, isometry: &Isometry3<f32>, prediction_distance: f32) -> Aabb {
         match self {
             ShapeWrapper::Sphere(sphere) => sphere.aabb(isometry).loosened(prediction_distance),
@@ -32,9 +130,32 @@ impl ShapeWrapper {
             ShapeWrapper::Triangle(triangle) => {
                 triangle.aabb(isometry).loosened(prediction_distance)
             }
+            ShapeWrapper::ConvexHull(hull) => hull.aabb(isometry).loosened(prediction_distance),
+            ShapeWrapper::TriMesh(trimesh) => trimesh.aabb(isometry).loosened(prediction_distance),
+            ShapeWrapper::Heightfield(heightfield) => {
+                heightfield.aabb(isometry).loosened(prediction_distance)
+            }
+            ShapeWrapper::Compound(compound) => compound
+                .compute_aabb(isometry)
+                .loosened(prediction_distance),
+            ShapeWrapper::RoundCuboid(cuboid) => {
+                cuboid.compute_aabb(isometry).loosened(prediction_distance)
+            }
+            ShapeWrapper::RoundCylinder(cylinder) => cylinder
+                .compute_aabb(isometry)
+                .loosened(prediction_distance),
+            ShapeWrapper::RoundCone(cone) => {
+                cone.compute_aabb(isometry).loosened(prediction_distance)
+            }
         }
     }
 
+    /// Returns the bounding box covering the shape along a linear motion.
+    pub fn swept_aabb(&self, isometry: &Isometry3<f32>, motion: &Vector3<f32>) -> Aabb {
+        let aabb = self.collision_aabb(isometry, 0.0);
+        aabb.merged(&Aabb::new(aabb.mins + motion, aabb.maxs + motion))
+    }
+
     pub fn cast_ray_and_get_normal(
         &self,
         isometry: &Isometry3<f32>,
@@ -64,6 +185,27 @@ impl ShapeWrapper {
             ShapeWrapper::Triangle(shape) => {
                 shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
             }
+            ShapeWrapper::ConvexHull(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
+            ShapeWrapper::TriMesh(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
+            ShapeWrapper::Heightfield(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
+            ShapeWrapper::Compound(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
+            ShapeWrapper::RoundCuboid(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
+            ShapeWrapper::RoundCylinder(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
+            ShapeWrapper::RoundCone(shape) => {
+                shape.cast_ray_and_get_normal(isometry, ray, max_time_to_impact, solid)
+            }
         }
     }
 
@@ -76,6 +218,32 @@ impl ShapeWrapper {
             ShapeWrapper::Cylinder(cylinder) => cylinder,
             ShapeWrapper::Cone(cone) => cone,
             ShapeWrapper::Triangle(triangle) => triangle,
+            ShapeWrapper::ConvexHull(hull) => hull,
+            ShapeWrapper::TriMesh(trimesh) => trimesh,
+            ShapeWrapper::Heightfield(heightfield) => heightfield,
+            ShapeWrapper::Compound(compound) => compound,
+            ShapeWrapper::RoundCuboid(cuboid) => cuboid,
+            ShapeWrapper::RoundCylinder(cylinder) => cylinder,
+            ShapeWrapper::RoundCone(cone) => cone,
+        }
+    }
+
+    fn into_shared_shape(self) -> SharedShape {
+        match self {
+            ShapeWrapper::Sphere(sphere) => SharedShape::new(sphere),
+            ShapeWrapper::Plane(plane) => SharedShape::new(plane),
+            ShapeWrapper::Capsule(capsule) => SharedShape::new(capsule),
+            ShapeWrapper::Cuboid(cuboid) => SharedShape::new(cuboid),
+            ShapeWrapper::Cylinder(cylinder) => SharedShape::new(cylinder),
+            ShapeWrapper::Cone(cone) => SharedShape::new(cone),
+            ShapeWrapper::Triangle(triangle) => SharedShape::new(triangle),
+            ShapeWrapper::ConvexHull(hull) => SharedShape::new(hull),
+            ShapeWrapper::TriMesh(trimesh) => SharedShape::new(trimesh),
+            ShapeWrapper::Heightfield(heightfield) => SharedShape::new(heightfield),
+            ShapeWrapper::Compound(compound) => SharedShape::new(compound),
+            ShapeWrapper::RoundCuboid(cuboid) => SharedShape::new(cuboid),
+            ShapeWrapper::RoundCylinder(cylinder) => SharedShape::new(cylinder),
+            ShapeWrapper::RoundCone(cone) => SharedShape::new(cone),
         }
     }
 
@@ -96,6 +264,54 @@ impl ShapeWrapper {
         result.unwrap_or_default()
     }
 
+    pub fn contact_manifolds(
+        &self,
+        isometry_a: &Isometry3<f32>,
+        other: &ShapeWrapper,
+        isometry_b: &Isometry3<f32>,
+        prediction: f32,
+        manifolds: &mut Vec<ContactManifold>,
+        workspace: &mut Option<ContactManifoldsWorkspace>,
+    ) {
+        let pos12 = isometry_a.inv_mul(isometry_b);
+        let result = DefaultQueryDispatcher.contact_manifolds(
+            &pos12,
+            self.as_parry_shape(),
+            other.as_parry_shape(),
+            prediction,
+            manifolds,
+            workspace,
+        );
+
+        if result.is_err() {
+            manifolds.clear();
+        }
+    }
+
+    /// Sweeps both shapes along their velocities and returns the first time of impact, if it
+    /// happens before `max_time_of_impact`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape(
+        &self,
+        isometry_a: &Isometry3<f32>,
+        velocity_a: &Vector3<f32>,
+        other: &ShapeWrapper,
+        isometry_b: &Isometry3<f32>,
+        velocity_b: &Vector3<f32>,
+        max_time_of_impact: f32,
+    ) -> Option<ShapeCastHit> {
+        let result = cast_shapes(
+            isometry_a,
+            velocity_a,
+            self.as_parry_shape(),
+            isometry_b,
+            velocity_b,
+            other.as_parry_shape(),
+            ShapeCastOptions::with_max_time_of_impact(max_time_of_impact),
+        );
+        result.unwrap_or_default()
+    }
+
     pub fn intersects(
         &self,
         isometry_a: &Isometry3<f32>,
@@ -111,6 +327,37 @@ impl ShapeWrapper {
 
         result.unwrap_or_default()
     }
+
+    pub fn contains_point(&self, isometry: &Isometry3<f32>, point: &Point<f32>) -> bool {
+        self.as_parry_shape().contains_point(isometry, point)
+    }
+
+    /// Projects a point on the shape, points inside solid shapes are projected on themselves.
+    pub fn project_point(
+        &self,
+        isometry: &Isometry3<f32>,
+        point: &Point<f32>,
+        solid: bool,
+    ) -> PointProjection {
+        self.as_parry_shape().project_point(isometry, point, solid)
+    }
+
+    /// Returns the distance between both shapes, 0.0 if they overlap, or `None` if parry does
+    /// not support this pair of shapes.
+    pub fn distance(
+        &self,
+        isometry_a: &Isometry3<f32>,
+        isometry_b: &Isometry3<f32>,
+        other: &ShapeWrapper,
+    ) -> Option<f32> {
+        distance(
+            isometry_a,
+            self.as_parry_shape(),
+            isometry_b,
+            other.as_parry_shape(),
+        )
+        .ok()
+    }
 }
 
 impl From<Collider> for ShapeWrapper {
@@ -132,13 +379,183 @@ impl From<&Collider> for ShapeWrapper {
                 ShapeWrapper::Cylinder(Cylinder::new(collider.height / 2.0, collider.radius))
             }
             ColliderType::Cone => {
-                ShapeWrapper::Cone(Cone::new(collider.radius, collider.height / 2.0))
+                ShapeWrapper::Cone(Cone::new(collider.height / 2.0, collider.radius))
             }
             ColliderType::Triangle => ShapeWrapper::Triangle(Triangle::new(
                 collider.point_a.into(),
                 collider.point_b.into(),
                 collider.point_c.into(),
             )),
+            ColliderType::ConvexHull => {
+                let points: Vec<Point<f32>> =
+                    collider.points.iter().map(|&point| point.into()).collect();
+                match ConvexPolyhedron::from_convex_hull(&points) {
+                    Some(hull) => ShapeWrapper::ConvexHull(hull),
+                    None => {
+                        warn!(
+                            "Collider {} has a degenerate convex hull, falling back to a point",
+                            collider.id
+                        );
+                        ShapeWrapper::Sphere(Ball::new(0.0))
+                    }
+                }
+            }
+            ColliderType::TriMesh => {
+                // parry panics on indices out of range
+                if !collider.has_valid_triangles() {
+                    warn!(
+                        "Collider {} has invalid triangle indices ({} indices for {} vertices), falling back to a point",
+                        collider.id,
+                        collider.indices.len(),
+                        collider.points.len()
+                    );
+                    return ShapeWrapper::Sphere(Ball::new(0.0));
+                }
+
+                let vertices = collider.points.iter().map(|&point| point.into()).collect();
+                match TriMesh::new(vertices, collider.triangles()) {
+                    Ok(trimesh) => ShapeWrapper::TriMesh(trimesh),
+                    Err(err) => {
+                        warn!(
+                            "Collider {} has an invalid triangle mesh ({:?}), falling back to a point",
+                            collider.id, err
+                        );
+                        ShapeWrapper::Sphere(Ball::new(0.0))
+                    }
+                }
+            }
+            ColliderType::Heightfield => {
+                let (rows, columns) = (collider.rows as usize, collider.columns as usize);
+                // parry needs at least one cell to build the heightfield
+                if rows < 2 || columns < 2 || collider.heights.len() != rows * columns {
+                    warn!(
+                        "Collider {} has an invalid heightfield ({} heights for a {}x{} grid), falling back to a point",
+                        collider.id,
+                        collider.heights.len(),
+                        rows,
+                        columns
+                    );
+                    return ShapeWrapper::Sphere(Ball::new(0.0));
+                }
+
+                let heights = DMatrix::from_row_slice(rows, columns, &collider.heights);
+                ShapeWrapper::Heightfield(HeightField::new(heights, collider.size.into()))
+            }
+            // The border is part of the collider dimensions, the inner shapes are shrunk by it
+            ColliderType::RoundCuboid => ShapeWrapper::RoundCuboid(RoundShape {
+                inner_shape: Cuboid::new(
+                    (collider.size / 2.0 - collider.border_radius)
+                        .max(Vec3::ZERO)
+                        .into(),
+                ),
+                border_radius: collider.border_radius,
+            }),
+            ColliderType::RoundCylinder => ShapeWrapper::RoundCylinder(RoundShape {
+                inner_shape: Cylinder::new(
+                    (collider.height / 2.0 - collider.border_radius).max(0.0),
+                    (collider.radius - collider.border_radius).max(0.0),
+                ),
+                border_radius: collider.border_radius,
+            }),
+            ColliderType::RoundCone => ShapeWrapper::RoundCone(RoundShape {
+                inner_shape: Cone::new(
+                    (collider.height / 2.0 - collider.border_radius).max(0.0),
+                    (collider.radius - collider.border_radius).max(0.0),
+                ),
+                border_radius: collider.border_radius,
+            }),
+            ColliderType::Compound => {
+                warn!(
+                    "Collider {} is a compound, its shape must be created with ShapeWrapper::new, falling back to a point",
+                    collider.id
+                );
+                ShapeWrapper::Sphere(Ball::new(0.0))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::{math::Quat, CompoundChild};
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= 1e-4 * expected.abs().max(1.0),
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn convex_hull_mass_properties_match_a_cube() {
+        let points: Vec<_> = (0..8)
+            .map(|i| {
+                let sign = |bit: i32| if i & bit == 0 { -1.0 } else { 1.0 };
+                Vec3::new(sign(1), sign(2), sign(4))
+            })
+            .collect();
+        let hull = Collider::convex_hull(1, &points).unwrap();
+
+        let properties = ShapeWrapper::new(&hull, &HashMap::new(), Vec3::ONE).mass_properties(2.0);
+
+        let mass = 2.0 * 8.0;
+        let inertia = mass * (2.0f32.powi(2) + 2.0f32.powi(2)) / 12.0;
+        assert_close(properties.mass, mass);
+        assert_close(properties.center_of_mass.length(), 0.0);
+        assert_close(properties.inertia_tensor.m11, inertia);
+        assert_close(properties.inertia_tensor.m22, inertia);
+        assert_close(properties.inertia_tensor.m33, inertia);
+        assert_close(properties.inertia_tensor.m12, 0.0);
+    }
+
+    #[test]
+    fn compound_mass_properties_combine_the_offset_children() {
+        let mut sphere = Collider::sphere(1, 0.5);
+        sphere.id = 2;
+        let colliders = HashMap::from([(sphere.id, sphere)]);
+        let compound = Collider::compound(
+            1,
+            vec![
+                CompoundChild::new(2, Vec3::ZERO, Quat::IDENTITY),
+                CompoundChild::new(2, Vec3::new(2.0, 0.0, 0.0), Quat::IDENTITY),
+            ],
+        );
+
+        let properties = ShapeWrapper::new(&compound, &colliders, Vec3::ONE).mass_properties(1.0);
+
+        // Each sphere is 1.0 away from the center of mass, between the two of them
+        let sphere_mass = 4.0 / 3.0 * PI * 0.5f32.powi(3);
+        let sphere_inertia = 2.0 / 5.0 * sphere_mass * 0.5f32.powi(2);
+        assert_close(properties.mass, 2.0 * sphere_mass);
+        assert_close(properties.center_of_mass.x, 1.0);
+        assert_close(properties.inertia_tensor.m11, 2.0 * sphere_inertia);
+        assert_close(
+            properties.inertia_tensor.m22,
+            2.0 * (sphere_inertia + sphere_mass),
+        );
+        assert_close(
+            properties.inertia_tensor.m33,
+            2.0 * (sphere_inertia + sphere_mass),
+        );
+    }
+
+    #[test]
+    fn trimesh_with_invalid_indices_falls_back_to_a_point() {
+        let vertices = vec![Vec3::ZERO, Vec3::X, Vec3::Z];
+        let out_of_range = Collider::trimesh(1, vertices.clone(), &[[0, 1, 2], [0, 2, 3]]);
+        let mut partial = Collider::trimesh(1, vertices.clone(), &[[0, 1, 2]]);
+        partial.indices.push(0);
+        let valid = Collider::trimesh(1, vertices, &[[0, 1, 2]]);
+
+        for collider in [out_of_range, partial] {
+            assert!(!collider.has_valid_triangles());
+            let shape = ShapeWrapper::new(&collider, &HashMap::new(), Vec3::ONE);
+            assert!(matches!(shape, ShapeWrapper::Sphere(_)));
         }
+        let shape = ShapeWrapper::new(&valid, &HashMap::new(), Vec3::ONE);
+        assert!(matches!(shape, ShapeWrapper::TriMesh(_)));
     }
 }
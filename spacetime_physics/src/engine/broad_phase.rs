@@ -0,0 +1,389 @@
+use std::collections::{HashMap, HashSet};
+
+use parry3d::{
+    bounding_volume::{Aabb, BoundingVolume},
+    na::Vector3,
+    partitioning::{Qbvh, QbvhUpdateWorkspace},
+    query::{
+        visitors::{
+            BoundingVolumeIntersectionsSimultaneousVisitor, BoundingVolumeIntersectionsVisitor,
+            RayIntersectionsVisitor,
+        },
+        Ray,
+    },
+};
+
+use crate::{PhysicsWorld, RigidBodyId, TriggerId};
+
+use super::{
+    collision_detection::Collidable, rigid_body_data::RigidBodyData, trigger_data::TriggerData,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ProxyKey {
+    RigidBody(RigidBodyId),
+    Trigger(TriggerId),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Proxy {
+    key: ProxyKey,
+    is_static: bool,
+    /// The bounding box stored in the QBVH, enlarged by the broad phase margin.
+    aabb: Aabb,
+}
+
+/// The QBVHs of a world, kept in the [`WorldCache`](super::world_cache::WorldCache) between
+/// steps and only updated for the entries that moved outside of their enlarged bounding box.
+///
+/// Static bodies are stored in their own tree so that the pairs between two of them, which
+/// can never interact, are not even traversed. Every leaf is identified by a slot that stays
+/// the same as long as its body or trigger exists, slots are reused once they are removed.
+pub(crate) struct BroadPhase {
+    static_tree: Qbvh<usize>,
+    dynamic_tree: Qbvh<usize>,
+    workspace: QbvhUpdateWorkspace,
+    slots: HashMap<ProxyKey, usize>,
+    proxies: Vec<Option<Proxy>>,
+    free_slots: Vec<usize>,
+    stack: Vec<(u32, u32)>,
+}
+
+impl Default for BroadPhase {
+    fn default() -> Self {
+        Self {
+            static_tree: Qbvh::new(),
+            dynamic_tree: Qbvh::new(),
+            workspace: QbvhUpdateWorkspace::default(),
+            slots: HashMap::new(),
+            proxies: Vec::new(),
+            free_slots: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl BroadPhase {
+    /// Synchronizes the trees with the bodies and triggers of the step and returns the
+    /// collidables indexed by their slot, `None` for the unused slots.
+    pub fn update(
+        &mut self,
+        world: &PhysicsWorld,
+        bodies: &[RigidBodyData],
+        triggers: &[TriggerData],
+    ) -> Vec<Option<Collidable>> {
+        let prediction_distance = world.prediction_distance();
+        let margin = world.broad_phase_margin();
+        let mut collidables = Vec::with_capacity(self.proxies.len());
+
+        for (i, body) in bodies.iter().enumerate() {
+            let is_static = !body.is_dynamic() && !body.is_kinematic();
+            let aabb = Self::body_aabb(world, body, prediction_distance);
+            let slot = self.upsert(ProxyKey::RigidBody(body.id), is_static, aabb, margin);
+            Self::set_collidable(
+                &mut collidables,
+                Collidable {
+                    id: body.id,
+                    rigidbody_index: i,
+                    trigger_index: 0,
+                    is_trigger: false,
+                    collidable_index: slot,
+                    collision_groups: body.collision_groups(),
+                },
+            );
+        }
+
+        for (i, trigger) in triggers.iter().enumerate() {
+            let aabb = trigger
+                .shape
+                .collision_aabb(&trigger.isometry, prediction_distance);
+            let slot = self.upsert(ProxyKey::Trigger(trigger.trigger_id), false, aabb, margin);
+            Self::set_collidable(
+                &mut collidables,
+                Collidable {
+                    id: trigger.trigger_id,
+                    rigidbody_index: 0,
+                    trigger_index: i,
+                    is_trigger: true,
+                    collidable_index: slot,
+                    collision_groups: trigger.collision_groups,
+                },
+            );
+        }
+
+        collidables.resize(self.proxies.len(), None);
+        for (slot, collidable) in collidables.iter().enumerate() {
+            if collidable.is_none() && self.proxies[slot].is_some() {
+                self.remove(slot);
+            }
+        }
+
+        self.refit();
+        collidables
+    }
+
+    /// Updates the bounding boxes of the bodies simulated during the step, i.e. the active ones,
+    /// so that the instant queries running before the next step use their final poses. Bodies
+    /// and triggers have already been inserted or removed by [`BroadPhase::update`].
+    pub fn refit_moved(&mut self, world: &PhysicsWorld, bodies: &[RigidBodyData]) {
+        let prediction_distance = world.prediction_distance();
+        let margin = world.broad_phase_margin();
+        for body in bodies.iter().filter(|body| body.is_active()) {
+            let aabb = Self::body_aabb(world, body, prediction_distance);
+            self.upsert(ProxyKey::RigidBody(body.id), false, aabb, margin);
+        }
+        self.refit();
+    }
+
+    /// Returns the pairs of collidables whose enlarged bounding boxes overlap, the one with the
+    /// smallest (is_trigger, id) first.
+    pub fn pairs(
+        &mut self,
+        collidables: &[Option<Collidable>],
+    ) -> HashSet<(Collidable, Collidable)> {
+        let mut pairs = HashSet::new();
+        let mut callback = |a: &usize, b: &usize| {
+            if let (Some(a), Some(b)) = (collidables[*a], collidables[*b]) {
+                if a != b && a.collision_groups.interacts_with(&b.collision_groups) {
+                    let (min, max) = if (a.is_trigger, a.id) < (b.is_trigger, b.id) {
+                        (a, b)
+                    } else {
+                        (b, a)
+                    };
+                    pairs.insert((min, max));
+                }
+            }
+            true
+        };
+
+        let mut visitor = BoundingVolumeIntersectionsSimultaneousVisitor::new(&mut callback);
+        self.dynamic_tree.traverse_bvtt_with_stack(
+            &self.dynamic_tree,
+            &mut visitor,
+            &mut self.stack,
+        );
+        let mut visitor = BoundingVolumeIntersectionsSimultaneousVisitor::new(&mut callback);
+        self.dynamic_tree.traverse_bvtt_with_stack(
+            &self.static_tree,
+            &mut visitor,
+            &mut self.stack,
+        );
+
+        pairs
+    }
+
+    /// Calls `callback` with the slot of every entry whose bounding box is hit by the ray,
+    /// stopping as soon as it returns false.
+    pub fn cast_ray(&self, ray: &Ray, max_distance: f32, mut callback: impl FnMut(usize) -> bool) {
+        let mut visit = |slot: &usize| callback(*slot);
+        let mut visitor = RayIntersectionsVisitor::new(ray, max_distance, &mut visit);
+        self.dynamic_tree.traverse_depth_first(&mut visitor);
+        let mut visitor = RayIntersectionsVisitor::new(ray, max_distance, &mut visit);
+        self.static_tree.traverse_depth_first(&mut visitor);
+    }
+
+    /// Calls `callback` with the slot of every entry whose bounding box intersects `aabb`,
+    /// stopping as soon as it returns false.
+    pub fn intersect_aabb(&self, aabb: &Aabb, mut callback: impl FnMut(usize) -> bool) {
+        let mut visit = |slot: &usize| callback(*slot);
+        let mut visitor = BoundingVolumeIntersectionsVisitor::new(aabb, &mut visit);
+        self.dynamic_tree.traverse_depth_first(&mut visitor);
+        let mut visitor = BoundingVolumeIntersectionsVisitor::new(aabb, &mut visit);
+        self.static_tree.traverse_depth_first(&mut visitor);
+    }
+
+    /// Returns the bodies whose bounding box intersects `aabb`.
+    pub fn rigid_bodies_in_aabb(&self, aabb: &Aabb) -> Vec<RigidBodyId> {
+        let mut ids = Vec::new();
+        self.intersect_aabb(aabb, |slot| {
+            if let Some(ProxyKey::RigidBody(id)) = self.key(slot) {
+                ids.push(id);
+            }
+            true
+        });
+        ids
+    }
+
+    /// Returns the triggers whose bounding box intersects `aabb`.
+    pub fn triggers_in_aabb(&self, aabb: &Aabb) -> Vec<TriggerId> {
+        let mut ids = Vec::new();
+        self.intersect_aabb(aabb, |slot| {
+            if let Some(ProxyKey::Trigger(id)) = self.key(slot) {
+                ids.push(id);
+            }
+            true
+        });
+        ids
+    }
+
+    /// Returns the bodies whose bounding box is hit by the ray.
+    pub fn rigid_bodies_along_ray(&self, ray: &Ray, max_distance: f32) -> Vec<RigidBodyId> {
+        let mut ids = Vec::new();
+        self.cast_ray(ray, max_distance, |slot| {
+            if let Some(ProxyKey::RigidBody(id)) = self.key(slot) {
+                ids.push(id);
+            }
+            true
+        });
+        ids
+    }
+
+    fn key(&self, slot: usize) -> Option<ProxyKey> {
+        self.proxies
+            .get(slot)
+            .and_then(|proxy| proxy.as_ref())
+            .map(|proxy| proxy.key)
+    }
+
+    /// Inserts an entry, or marks it for refit if it left its enlarged bounding box or shrank
+    /// by more than the margin, and returns its slot.
+    fn upsert(&mut self, key: ProxyKey, is_static: bool, aabb: Aabb, margin: f32) -> usize {
+        let enlarged = aabb.loosened(margin);
+
+        let Some(&slot) = self.slots.get(&key) else {
+            let slot = self.free_slots.pop().unwrap_or(self.proxies.len());
+            if slot == self.proxies.len() {
+                self.proxies.push(None);
+            }
+            self.proxies[slot] = Some(Proxy {
+                key,
+                is_static,
+                aabb: enlarged,
+            });
+            self.slots.insert(key, slot);
+            self.tree_mut(is_static).pre_update_or_insert(slot);
+            return slot;
+        };
+
+        let proxy = self.proxies[slot].as_mut().unwrap();
+        let was_static = proxy.is_static;
+        let moved =
+            !proxy.aabb.contains(&aabb) || !aabb.loosened(2.0 * margin).contains(&proxy.aabb);
+        if !moved && was_static == is_static {
+            return slot;
+        }
+
+        proxy.aabb = enlarged;
+        proxy.is_static = is_static;
+        if was_static != is_static {
+            self.tree_mut(was_static).remove(slot);
+        }
+        self.tree_mut(is_static).pre_update_or_insert(slot);
+        slot
+    }
+
+    fn refit(&mut self) {
+        let proxies = &self.proxies;
+        let aabb_of = |slot: &usize| proxies[*slot].map_or_else(Aabb::new_invalid, |p| p.aabb);
+        self.static_tree.refit(0.0, &mut self.workspace, aabb_of);
+        self.dynamic_tree.refit(0.0, &mut self.workspace, aabb_of);
+        self.static_tree.rebalance(0.0, &mut self.workspace);
+        self.dynamic_tree.rebalance(0.0, &mut self.workspace);
+    }
+
+    fn remove(&mut self, slot: usize) {
+        if let Some(proxy) = self.proxies[slot].take() {
+            self.tree_mut(proxy.is_static).remove(slot);
+            self.slots.remove(&proxy.key);
+            self.free_slots.push(slot);
+        }
+    }
+
+    fn tree_mut(&mut self, is_static: bool) -> &mut Qbvh<usize> {
+        if is_static {
+            &mut self.static_tree
+        } else {
+            &mut self.dynamic_tree
+        }
+    }
+
+    fn set_collidable(collidables: &mut Vec<Option<Collidable>>, collidable: Collidable) {
+        let slot = collidable.collidable_index;
+        if slot >= collidables.len() {
+            collidables.resize(slot + 1, None);
+        }
+        collidables[slot] = Some(collidable);
+    }
+
+    /// Returns the bounding box of a body, swept along its expected motion during the step when
+    /// continuous collision detection is enabled so that the broad phase reports what it could hit.
+    fn body_aabb(world: &PhysicsWorld, body: &RigidBodyData, prediction_distance: f32) -> Aabb {
+        let aabb = body
+            .shape()
+            .collision_aabb(&body.collider_isometry(), prediction_distance);
+        if !body.is_ccd_enabled() || !body.is_active() {
+            return aabb;
+        }
+
+        let velocity = body.linear_velocity() + 0.5 * world.gravity * world.time_step;
+        let motion: Vector3<f32> = (velocity * world.time_step).into();
+        aabb.merged(&Aabb::new(aabb.mins + motion, aabb.maxs + motion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{math::Vec3, utils::sphere_body};
+
+    fn slots(collidables: &[Option<Collidable>]) -> HashMap<RigidBodyId, usize> {
+        collidables
+            .iter()
+            .flatten()
+            .map(|collidable| (collidable.id, collidable.collidable_index))
+            .collect()
+    }
+
+    fn bodies(ids: &[u64]) -> Vec<RigidBodyData> {
+        ids.iter()
+            .map(|&id| sphere_body(id, Vec3::new(10.0 * id as f32, 0.0, 0.0)))
+            .collect()
+    }
+
+    #[test]
+    fn slots_are_kept_and_reused_once_removed() {
+        let world = PhysicsWorld::builder().build();
+        let mut broad_phase = BroadPhase::default();
+
+        let first = slots(&broad_phase.update(&world, &bodies(&[1, 2, 3]), &[]));
+        let second = broad_phase.update(&world, &bodies(&[1, 3]), &[]);
+        assert_eq!(second.len(), 3);
+        assert!(second[first[&2]].is_none());
+        assert_eq!(
+            slots(&second),
+            HashMap::from([(1, first[&1]), (3, first[&3])])
+        );
+
+        let third = slots(&broad_phase.update(&world, &bodies(&[1, 3, 4]), &[]));
+        assert_eq!(third[&4], first[&2]);
+        assert_eq!(broad_phase.proxies.len(), 3);
+    }
+
+    #[test]
+    fn removed_bodies_are_not_queried_anymore() {
+        let world = PhysicsWorld::builder().build();
+        let mut broad_phase = BroadPhase::default();
+        let aabb = Aabb::new([15.0, -1.0, -1.0].into(), [25.0, 1.0, 1.0].into());
+
+        broad_phase.update(&world, &bodies(&[1, 2]), &[]);
+        assert_eq!(broad_phase.rigid_bodies_in_aabb(&aabb), vec![2]);
+
+        broad_phase.update(&world, &bodies(&[1]), &[]);
+        assert!(broad_phase.rigid_bodies_in_aabb(&aabb).is_empty());
+    }
+
+    #[test]
+    fn refit_moved_follows_the_active_bodies() {
+        let world = PhysicsWorld::builder().build();
+        let mut broad_phase = BroadPhase::default();
+        let mut bodies = bodies(&[1]);
+        let aabb = Aabb::new([-1.0, 4.0, -1.0].into(), [1.0, 6.0, 1.0].into());
+
+        broad_phase.update(&world, &bodies, &[]);
+        bodies[0].set_position(Vec3::new(0.0, 5.0, 0.0));
+        assert!(broad_phase.rigid_bodies_in_aabb(&aabb).is_empty());
+
+        broad_phase.refit_moved(&world, &bodies);
+        assert_eq!(broad_phase.rigid_bodies_in_aabb(&aabb), vec![1]);
+    }
+}
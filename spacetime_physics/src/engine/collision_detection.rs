@@ -2,23 +2,22 @@ use std::collections::{HashMap, HashSet};
 
 use log::debug;
 use parry3d::{
-    bounding_volume::Aabb,
-    partitioning::{IndexedData, Qbvh as QbvhImpl},
-    query::{
-        visitors::{BoundingVolumeIntersectionsSimultaneousVisitor, RayIntersectionsVisitor},
-        Ray,
-    },
+    na::{Isometry3, Vector3},
+    query::Ray,
 };
 use spacetimedb::ReducerContext;
 
 use crate::{
-    test_collision, utils::get_bodies_direct, PhysicsWorld, RayCast, RayCastHit, RaycastId,
+    manifold_collision_point, queries::cast_against_body, update_contact_manifolds,
+    utils::get_bodies_direct, CollisionGroups, PhysicsWorld, RayCast, RayCastHit, RaycastId,
 };
 
 use super::{
-    constraints::PenetrationConstraint, rigid_body_data::RigidBodyData, trigger_data::TriggerData,
+    broad_phase::BroadPhase, constraints::PenetrationConstraint, rigid_body_data::RigidBodyData,
+    shape_cast_data::ShapeCastData, trigger_data::TriggerData, world_cache::WorldCache,
 };
 
+/// A body or trigger of the current step, `collidable_index` being its slot in the broad phase.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Collidable {
     pub id: u64,
@@ -26,22 +25,7 @@ pub struct Collidable {
     pub trigger_index: usize,
     pub is_trigger: bool,
     pub collidable_index: usize,
-}
-
-impl IndexedData for Collidable {
-    fn default() -> Self {
-        Self {
-            id: 0,
-            rigidbody_index: 0,
-            trigger_index: 0,
-            is_trigger: false,
-            collidable_index: 0,
-        }
-    }
-
-    fn index(&self) -> usize {
-        self.collidable_index
-    }
+    pub collision_groups: CollisionGroups,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -51,19 +35,18 @@ pub struct RayCastEntry {
 }
 
 pub struct CollisionDetection {
-    qbvh: QbvhImpl<Collidable>,
-    stack: Vec<(u32, u32)>,
     pairs: HashSet<(Collidable, Collidable)>,
     raycasts_pairs: HashMap<RayCastEntry, HashSet<Collidable>>,
+    /// The bodies overlapping the swept bounding box of each shape cast, keyed by its index.
+    shapecasts_pairs: HashMap<usize, HashSet<Collidable>>,
 }
 
 impl CollisionDetection {
     pub fn new() -> Self {
         Self {
-            qbvh: QbvhImpl::<Collidable>::new(),
-            stack: Vec::new(),
             pairs: HashSet::new(),
             raycasts_pairs: HashMap::new(),
+            shapecasts_pairs: HashMap::new(),
         }
     }
 
@@ -77,26 +60,29 @@ impl CollisionDetection {
         bodies: &[RigidBodyData],
         triggers: &[TriggerData],
         raycasts: &[RayCast],
+        shapecasts: &[ShapeCastData],
+        broad_phase: &mut BroadPhase,
     ) {
         let sw = world.stopwatch("broad_phase");
-        let prediction_distance = world.prediction_distance();
 
-        let rebuild_sw = world.stopwatch("broad_phase_rebuild");
-        let collidables = self.collect_collidables(bodies, triggers, prediction_distance);
-        self.qbvh
-            .clear_and_rebuild(collidables.into_iter(), world.qvbh_dilation_factor);
-        rebuild_sw.end();
+        let update_sw = world.stopwatch("broad_phase_update");
+        let collidables = broad_phase.update(world, bodies, triggers);
+        update_sw.end();
 
-        self.run_broad_phase_pairs(world);
-        self.run_broad_phase_raycast_pairs(world, raycasts);
+        self.run_broad_phase_pairs(world, broad_phase, &collidables);
+        self.run_broad_phase_raycast_pairs(world, raycasts, broad_phase, &collidables);
+        self.run_broad_phase_shapecast_pairs(world, shapecasts, broad_phase, &collidables);
 
         sw.end();
     }
 
+    /// Updates the cached contact manifolds of every overlapping pair and creates one constraint
+    /// per manifold point, warm started from the previous substep.
     pub fn narrow_phase_constraints(
         &self,
         world: &PhysicsWorld,
-        bodies: &mut [RigidBodyData],
+        bodies: &[RigidBodyData],
+        cache: &mut WorldCache,
     ) -> Vec<PenetrationConstraint> {
         let sw = world.stopwatch("narrow_phase");
         let mut constraints = Vec::new();
@@ -107,13 +93,31 @@ impl CollisionDetection {
             }
 
             let (body_a, body_b) = get_bodies_direct(a.rigidbody_index, b.rigidbody_index, bodies);
+            if !body_a.is_active() && !body_b.is_active() {
+                continue; // Nothing to simulate between sleeping, static or kinematic bodies
+            }
 
-            if let Some(collision) = test_collision(body_a, body_b, world.precision) {
-                if collision.distance >= 0.0 {
-                    continue; // No penetration
-                }
+            let pair = cache.contacts.entry((body_a.id, body_b.id)).or_default();
+            update_contact_manifolds(
+                body_a,
+                body_b,
+                world.precision,
+                &mut pair.manifolds,
+                &mut pair.workspace,
+            );
 
-                constraints.push(PenetrationConstraint::new(body_a, body_b, collision, 0.0));
+            for (manifold_index, manifold) in pair.manifolds.iter().enumerate() {
+                for (point_index, contact) in manifold.points.iter().enumerate() {
+                    let collision = manifold_collision_point(body_a, body_b, manifold, contact);
+                    constraints.push(
+                        PenetrationConstraint::new(body_a, body_b, collision, 0.0).with_warm_start(
+                            manifold_index,
+                            point_index,
+                            &contact.data,
+                            world.warm_start_coefficient,
+                        ),
+                    );
+                }
             }
         }
 
@@ -128,6 +132,63 @@ impl CollisionDetection {
         constraints
     }
 
+    /// Sweeps the bodies with continuous collision detection enabled from their position before
+    /// the substep integration to their current one and moves them back to their first impact,
+    /// the contact is then handled by the narrow phase of the next substep.
+    pub fn continuous_collisions(&self, world: &PhysicsWorld, bodies: &mut [RigidBodyData]) {
+        let sw = world.stopwatch("continuous_collisions");
+        let mut impacts: HashMap<usize, f32> = HashMap::new();
+
+        for (a, b) in &self.pairs {
+            if a.is_trigger || b.is_trigger {
+                continue;
+            }
+
+            let (body_a, body_b) = get_bodies_direct(a.rigidbody_index, b.rigidbody_index, bodies);
+            if !Self::needs_ccd(body_a) && !Self::needs_ccd(body_b) {
+                continue;
+            }
+
+            let Some(hit) = body_a.shape().cast_shape(
+                &Self::sweep_start(body_a),
+                &Self::sweep_motion(body_a),
+                body_b.shape(),
+                &Self::sweep_start(body_b),
+                &Self::sweep_motion(body_b),
+                1.0,
+            ) else {
+                continue;
+            };
+
+            // Bodies already touching at the beginning of the substep are handled by the narrow phase
+            if hit.time_of_impact <= 0.0 {
+                continue;
+            }
+
+            for (collidable, body) in [(a, body_a), (b, body_b)] {
+                if Self::needs_ccd(body) {
+                    let toi = impacts.entry(collidable.rigidbody_index).or_insert(1.0);
+                    *toi = toi.min(hit.time_of_impact);
+                }
+            }
+        }
+
+        for (index, toi) in impacts {
+            let body = &mut bodies[index];
+            let motion = body.position() - body.previous_position();
+            body.set_position(body.previous_position() + motion * toi);
+            body.set_ccd_impact(true);
+
+            if world.debug_narrow_phase() {
+                debug!(
+                    "[PhysicsWorld#{}] [CCD] body {} clamped at time of impact {}",
+                    world.id, body.id, toi
+                );
+            }
+        }
+        sw.end();
+    }
+
     pub fn narrow_phase_triggers(
         &self,
         ctx: &ReducerContext,
@@ -149,7 +210,7 @@ impl CollisionDetection {
 
             if trigger
                 .shape
-                .intersects(&trigger.isometry, &body.into(), body.shape())
+                .intersects(&trigger.isometry, &body.collider_isometry(), body.shape())
             {
                 trigger.new_entities_inside.insert(body.id);
             } else {
@@ -199,7 +260,7 @@ impl CollisionDetection {
             for broad_hit in broad_hits {
                 let body = &bodies[broad_hit.rigidbody_index];
                 if let Some(intersection) = body.shape().cast_ray_and_get_normal(
-                    &body.into(),
+                    &body.collider_isometry(),
                     &ray,
                     raycast.max_distance,
                     raycast.solid,
@@ -230,44 +291,97 @@ impl CollisionDetection {
         sw.end();
     }
 
-    fn run_broad_phase_pairs(&mut self, world: &PhysicsWorld) {
+    pub fn narrow_phase_shapecast(
+        &self,
+        ctx: &ReducerContext,
+        world: &PhysicsWorld,
+        bodies: &[RigidBodyData],
+        shapecasts: &mut [ShapeCastData],
+    ) {
+        let sw = world.stopwatch("narrow_phase_shapecast");
+        for (index, broad_hits) in self.shapecasts_pairs.iter() {
+            let data = &mut shapecasts[*index];
+            let mut hits: Vec<_> = broad_hits
+                .iter()
+                .filter_map(|broad_hit| {
+                    cast_against_body(
+                        &data.shape,
+                        &data.isometry,
+                        &data.velocity,
+                        data.shape_cast.max_distance,
+                        &bodies[broad_hit.rigidbody_index],
+                    )
+                })
+                .collect();
+            hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+            // Hits are matched by body, the contact points move with the cast shape every tick
+            let shape_cast = &mut data.shape_cast;
+            let previous_bodies: HashSet<_> = shape_cast
+                .hits
+                .iter()
+                .map(|hit| hit.rigid_body_id)
+                .collect();
+            let current_bodies: HashSet<_> = hits.iter().map(|hit| hit.rigid_body_id).collect();
+            shape_cast.added_hits = hits
+                .iter()
+                .filter(|hit| !previous_bodies.contains(&hit.rigid_body_id))
+                .cloned()
+                .collect();
+            shape_cast.removed_hits = shape_cast
+                .hits
+                .iter()
+                .filter(|hit| !current_bodies.contains(&hit.rigid_body_id))
+                .cloned()
+                .collect();
+            let is_different = previous_bodies != current_bodies;
+            shape_cast.hits = hits;
+
+            if world.debug_raycasts() && is_different {
+                debug!(
+                    "[PhysicsWorld#{}] [ShapeCast] ShapeCast#{} hits: {:?}, added: {:?}, removed: {:?}",
+                    world.id, shape_cast.id, shape_cast.hits, shape_cast.added_hits, shape_cast.removed_hits
+                );
+            }
+            data.update(ctx);
+        }
+        sw.end();
+    }
+
+    fn run_broad_phase_pairs(
+        &mut self,
+        world: &PhysicsWorld,
+        broad_phase: &mut BroadPhase,
+        collidables: &[Option<Collidable>],
+    ) {
         let traverse_sw = world.stopwatch("broad_phase_traverse");
-        let mut pairs = HashSet::new();
-        let mut visitor = BoundingVolumeIntersectionsSimultaneousVisitor::new(
-            |a: &Collidable, b: &Collidable| {
-                if a != b {
-                    let (min_id, max_id) = if a.collidable_index < b.collidable_index {
-                        (a, b)
-                    } else {
-                        (b, a)
-                    };
-                    pairs.insert((*min_id, *max_id));
-                }
-                true
-            },
-        );
-        self.qbvh
-            .traverse_bvtt_with_stack(&self.qbvh, &mut visitor, &mut self.stack);
+        self.pairs = broad_phase.pairs(collidables);
         traverse_sw.end();
-
-        self.pairs = pairs;
     }
 
-    fn run_broad_phase_raycast_pairs(&mut self, world: &PhysicsWorld, raycasts: &[RayCast]) {
+    fn run_broad_phase_raycast_pairs(
+        &mut self,
+        world: &PhysicsWorld,
+        raycasts: &[RayCast],
+        broad_phase: &BroadPhase,
+        collidables: &[Option<Collidable>],
+    ) {
         let traverse_sw = world.stopwatch("broad_phase_raycast_traverse");
         for (i, raycast) in raycasts.iter().enumerate() {
             let ray = Ray::new(raycast.origin.into(), raycast.direction.into());
             let mut entities = HashSet::new();
-            let mut callback = |collidable: &Collidable| {
-                if !collidable.is_trigger {
-                    entities.insert(*collidable);
+            broad_phase.cast_ray(&ray, raycast.max_distance, |slot| {
+                if let Some(collidable) = collidables[slot] {
+                    if !collidable.is_trigger
+                        && collidable
+                            .collision_groups
+                            .interacts_with(&raycast.collision_groups)
+                    {
+                        entities.insert(collidable);
+                    }
                 }
                 true
-            };
-            let mut visitor =
-                RayIntersectionsVisitor::new(&ray, raycast.max_distance, &mut callback);
-
-            self.qbvh.traverse_depth_first(&mut visitor);
+            });
 
             if !entities.is_empty() {
                 self.raycasts_pairs.insert(
@@ -282,44 +396,52 @@ impl CollisionDetection {
         traverse_sw.end();
     }
 
-    fn collect_collidables(
-        &self,
-        bodies: &[RigidBodyData],
-        triggers: &[TriggerData],
-        prediction_distance: f32,
-    ) -> Vec<(Collidable, Aabb)> {
-        let mut collidables: Vec<(Collidable, Aabb)> =
-            Vec::with_capacity(bodies.len() + triggers.len());
-        for (i, entity) in bodies.iter().enumerate() {
-            collidables.push((
-                Collidable {
-                    id: entity.id,
-                    rigidbody_index: i,
-                    trigger_index: 0,
-                    is_trigger: false,
-                    collidable_index: i,
-                },
-                entity
-                    .shape()
-                    .collision_aabb(&entity.into(), prediction_distance),
-            ));
+    /// Collects the candidate bodies of every shape cast, shape casts without any are kept so
+    /// that their previous hits get removed.
+    fn run_broad_phase_shapecast_pairs(
+        &mut self,
+        world: &PhysicsWorld,
+        shapecasts: &[ShapeCastData],
+        broad_phase: &BroadPhase,
+        collidables: &[Option<Collidable>],
+    ) {
+        let traverse_sw = world.stopwatch("broad_phase_shapecast_traverse");
+        for (i, data) in shapecasts.iter().enumerate() {
+            let mut entities = HashSet::new();
+            broad_phase.intersect_aabb(&data.swept_aabb(), |slot| {
+                if let Some(collidable) = collidables[slot] {
+                    if !collidable.is_trigger
+                        && collidable
+                            .collision_groups
+                            .interacts_with(&data.shape_cast.collision_groups)
+                    {
+                        entities.insert(collidable);
+                    }
+                }
+                true
+            });
+            self.shapecasts_pairs.insert(i, entities);
         }
-        let entities_count = bodies.len();
-        for (i, trigger) in triggers.iter().enumerate() {
-            collidables.push((
-                Collidable {
-                    id: trigger.trigger_id,
-                    rigidbody_index: 0,
-                    trigger_index: i,
-                    is_trigger: true,
-                    collidable_index: i + entities_count,
-                },
-                trigger
-                    .shape
-                    .collision_aabb(&trigger.isometry, prediction_distance),
-            ));
+        traverse_sw.end();
+    }
+
+    fn needs_ccd(body: &RigidBodyData) -> bool {
+        body.is_ccd_enabled() && body.is_active()
+    }
+
+    /// Returns the pose of a body before the substep integration, the rotation is kept fixed as
+    /// the sweep is linear.
+    fn sweep_start(body: &RigidBodyData) -> Isometry3<f32> {
+        Isometry3::from_parts(body.previous_position().into(), body.rotation().into())
+            * body.local_collider_isometry()
+    }
+
+    /// Returns the motion of a body during the substep, non dynamic bodies are considered fixed.
+    fn sweep_motion(body: &RigidBodyData) -> Vector3<f32> {
+        if !body.is_active() {
+            return Vector3::zeros();
         }
 
-        collidables
+        (body.position() - body.previous_position()).into()
     }
 }
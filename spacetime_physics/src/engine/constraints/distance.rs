@@ -0,0 +1,259 @@
+use std::fmt::Display;
+
+use log::debug;
+use spacetimedb::ReducerContext;
+
+use crate::{
+    math::Vec3,
+    utils::{get_bodies_mut, get_body_mut},
+    DistanceJoint, DistanceJointId, PhysicsWorld, PhysicsWorldId, RigidBodyData,
+};
+
+use super::{position::PositionConstraint, Constraint};
+
+#[derive(Debug, Clone)]
+pub struct DistanceConstraint {
+    pub id: DistanceJointId,
+    pub a: u64,
+    /// `None` when body A is attached to a fixed point in the world.
+    pub b: Option<u64>,
+    pub local_anchor_a: Vec3,
+    pub anchor_b: Vec3,
+    pub min_length: f32,
+    pub max_length: f32,
+    pub compliance: f32,
+    pub damping: f32,
+    pub lagrange: f32,
+}
+
+impl DistanceConstraint {
+    pub fn new(joint: &DistanceJoint) -> Self {
+        Self {
+            id: joint.id,
+            a: joint.body_a,
+            b: joint.body_b,
+            local_anchor_a: joint.local_anchor_a,
+            anchor_b: joint.anchor_b,
+            min_length: joint.min_length,
+            max_length: joint.max_length,
+            compliance: joint.compliance,
+            damping: joint.damping,
+            lagrange: 0.0,
+        }
+    }
+
+    /// Loads the distance joints of a world, skipping the ones referencing bodies that do not exist.
+    /// `bodies` must be sorted by id.
+    pub fn collect(
+        ctx: &ReducerContext,
+        world_id: PhysicsWorldId,
+        bodies: &[RigidBodyData],
+    ) -> Vec<Self> {
+        let exists = |id: u64| bodies.binary_search_by_key(&id, |b| b.id).is_ok();
+
+        DistanceJoint::all(ctx, world_id)
+            .filter(|joint| joint.body_b != Some(joint.body_a))
+            .filter(|joint| exists(joint.body_a) && joint.body_b.is_none_or(exists))
+            .map(|joint| DistanceConstraint::new(&joint))
+            .collect()
+    }
+
+    /// Lagrange multipliers are accumulated over the position iterations of a single substep.
+    pub fn reset_lagrange(&mut self) {
+        self.lagrange = 0.0;
+    }
+
+    /// Returns the constraint error and the direction along which anchor A has to move away
+    /// from anchor B, or `None` if the distance is within the limits.
+    fn error(&self, anchor_a: Vec3, anchor_b: Vec3) -> Option<(f32, Vec3)> {
+        let delta = anchor_a - anchor_b;
+        let length = delta.length();
+        if length <= f32::EPSILON {
+            return None;
+        }
+
+        let n = delta / length;
+        if length > self.max_length {
+            Some((length - self.max_length, n))
+        } else if length < self.min_length {
+            Some((self.min_length - length, -n))
+        } else {
+            None
+        }
+    }
+
+    /// XPBD Lagrange multiplier update with damping (equation 26 of "Detailed Rigid Body
+    /// Simulation with Extended Position Based Dynamics"), `velocity_term` being the motion of
+    /// the anchors along the constraint gradient during the substep.
+    fn compute_damped_lagrange_update(
+        &self,
+        c: f32,
+        w_sum: f32,
+        velocity_term: f32,
+        dt: f32,
+    ) -> f32 {
+        let a_tilde = self.compliance / dt.powi(2);
+        let gamma = self.compliance * self.damping / dt;
+        let denominator = (1.0 + gamma) * w_sum + a_tilde;
+        if denominator <= f32::EPSILON {
+            return 0.0;
+        }
+
+        (-c - a_tilde * self.lagrange - gamma * velocity_term) / denominator
+    }
+
+    fn solve_pair(
+        &mut self,
+        world: &PhysicsWorld,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        dt: f32,
+    ) {
+        if !body_a.is_active() && !body_b.is_active() {
+            return;
+        }
+
+        let ra = body_a.lever_arm(self.local_anchor_a);
+        let rb = body_b.lever_arm(self.anchor_b);
+        let anchor_a = body_a.world_point(self.local_anchor_a);
+        let anchor_b = body_b.world_point(self.anchor_b);
+
+        let Some((c, n)) = self.error(anchor_a, anchor_b) else {
+            return;
+        };
+
+        let previous_anchor_a =
+            body_a.previous_position() + body_a.previous_rotation().rotate(self.local_anchor_a);
+        let previous_anchor_b =
+            body_b.previous_position() + body_b.previous_rotation().rotate(self.anchor_b);
+        let velocity_term = n.dot((anchor_a - previous_anchor_a) - (anchor_b - previous_anchor_b));
+
+        let wa = self.compute_generalized_inverse_mass(body_a, &ra, &n);
+        let wb = self.compute_generalized_inverse_mass(body_b, &rb, &n);
+
+        let delta_lagrange = self.compute_damped_lagrange_update(c, wa + wb, velocity_term, dt);
+        self.lagrange += delta_lagrange;
+        self.apply_position_correction(body_a, body_b, delta_lagrange, &n, &ra, &rb);
+
+        if world.debug_constraints() {
+            debug!(
+                "[DistanceConstraint] joint: {}, a: {}, b: {}, error: {}, lagrange: {}",
+                self.id, self.a, body_b.id, c, self.lagrange
+            );
+        }
+    }
+
+    fn solve_world_anchor(&mut self, world: &PhysicsWorld, body: &mut RigidBodyData, dt: f32) {
+        if !body.is_active() {
+            return;
+        }
+
+        let ra = body.lever_arm(self.local_anchor_a);
+        let anchor_a = body.world_point(self.local_anchor_a);
+
+        let Some((c, n)) = self.error(anchor_a, self.anchor_b) else {
+            return;
+        };
+
+        let previous_anchor_a =
+            body.previous_position() + body.previous_rotation().rotate(self.local_anchor_a);
+        let velocity_term = n.dot(anchor_a - previous_anchor_a);
+
+        let w = self.compute_generalized_inverse_mass(body, &ra, &n);
+
+        let delta_lagrange = self.compute_damped_lagrange_update(c, w, velocity_term, dt);
+        if delta_lagrange.abs() < f32::EPSILON {
+            return;
+        }
+        self.lagrange += delta_lagrange;
+        Self::apply_body_correction(body, &(delta_lagrange * n), &ra, 1.0);
+
+        if world.debug_constraints() {
+            debug!(
+                "[DistanceConstraint] joint: {}, a: {}, world anchor: {}, error: {}, lagrange: {}",
+                self.id, self.a, self.anchor_b, c, self.lagrange
+            );
+        }
+    }
+}
+
+impl Constraint for DistanceConstraint {
+    fn solve(&mut self, world: &PhysicsWorld, bodies: &mut [RigidBodyData], dt: f32) {
+        match self.b {
+            Some(b) => {
+                let (body_a, body_b) = get_bodies_mut(self.a, b, bodies);
+                self.solve_pair(world, body_a, body_b, dt);
+            }
+            None => {
+                let body = get_body_mut(self.a, bodies);
+                self.solve_world_anchor(world, body, dt);
+            }
+        }
+    }
+}
+
+impl PositionConstraint for DistanceConstraint {}
+
+impl Display for DistanceConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DistanceConstraint(id: {}, a: {}, b: {:?}, min_length: {}, max_length: {}, compliance: {}, damping: {}, lagrange: {})",
+            self.id,
+            self.a,
+            self.b,
+            self.min_length,
+            self.max_length,
+            self.compliance,
+            self.damping,
+            self.lagrange
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sphere_body;
+
+    fn solve(constraint: &mut DistanceConstraint, bodies: &mut [RigidBodyData]) {
+        let world = PhysicsWorld::builder().build();
+        for _ in 0..50 {
+            constraint.reset_lagrange();
+            constraint.solve(&world, bodies, 1.0 / 60.0);
+        }
+    }
+
+    #[test]
+    fn rope_pulls_the_body_back_to_its_max_length() {
+        let mut bodies = vec![sphere_body(1, Vec3::new(0.0, -3.0, 0.0))];
+        let joint = DistanceJoint::builder().body_a(1).max_length(2.0).build();
+        let mut constraint = DistanceConstraint::new(&joint);
+
+        solve(&mut constraint, &mut bodies);
+
+        assert!((bodies[0].position().length() - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rod_keeps_two_bodies_between_its_lengths() {
+        let mut bodies = vec![
+            sphere_body(1, Vec3::ZERO),
+            sphere_body(2, Vec3::new(0.5, 0.0, 0.0)),
+        ];
+        let joint = DistanceJoint::builder()
+            .body_a(1)
+            .body_b(2)
+            .min_length(1.0)
+            .max_length(1.0)
+            .build();
+        let mut constraint = DistanceConstraint::new(&joint);
+
+        solve(&mut constraint, &mut bodies);
+
+        let length = (bodies[1].position() - bodies[0].position()).length();
+        assert!((length - 1.0).abs() < 1e-3);
+        // Both bodies have the same mass so they move by the same amount
+        assert!((bodies[0].position().x + 0.25).abs() < 1e-3);
+    }
+}
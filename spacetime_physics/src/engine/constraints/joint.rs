@@ -0,0 +1,577 @@
+use std::{f32::consts::PI, fmt::Display};
+
+use log::debug;
+use spacetimedb::ReducerContext;
+
+use crate::{
+    math::{Quat, Vec3},
+    utils::get_bodies_mut,
+    Joint, JointId, JointMotor, JointType, PhysicsWorld, PhysicsWorldId, RigidBodyData,
+};
+
+use super::{position::PositionConstraint, Constraint};
+
+#[derive(Debug, Clone)]
+pub struct JointConstraint {
+    pub id: JointId,
+    pub joint_type: JointType,
+    pub a: u64,
+    pub b: u64,
+    pub local_anchor_a: Vec3,
+    pub local_anchor_b: Vec3,
+    pub local_axis_a: Vec3,
+    pub local_axis_b: Vec3,
+    /// The zero angle references of revolute joints, matching when the bodies are at rest.
+    pub local_reference_a: Vec3,
+    pub local_reference_b: Vec3,
+    pub rest_rotation: Quat,
+    pub compliance: f32,
+    pub angular_compliance: f32,
+    pub limits: Option<(f32, f32)>,
+    pub motor: JointMotor,
+    pub motor_target: f32,
+    pub motor_compliance: f32,
+    pub motor_max_force: f32,
+    pub break_force: f32,
+    pub break_torque: f32,
+    pub position_lagrange: f32,
+    pub angular_lagrange: f32,
+    pub limit_lagrange: f32,
+    pub motor_lagrange: f32,
+    pub force: f32,
+    pub torque: f32,
+    pub broken: bool,
+}
+
+impl JointConstraint {
+    pub fn new(joint: &Joint) -> Self {
+        let rest_rotation = joint.rest_rotation.normalize();
+        // Both references are the same vector once body B is at its rest rotation, otherwise
+        // the angle of the joint would depend on the axis each body was built with
+        let local_reference_a = any_orthogonal(joint.local_axis_a.normalize());
+        let local_reference_b = rest_rotation.inverse().rotate(local_reference_a);
+
+        Self {
+            id: joint.id,
+            joint_type: joint.joint_type,
+            a: joint.body_a,
+            b: joint.body_b,
+            local_anchor_a: joint.local_anchor_a,
+            local_anchor_b: joint.local_anchor_b,
+            local_axis_a: joint.local_axis_a.normalize(),
+            local_axis_b: joint.local_axis_b.normalize(),
+            local_reference_a,
+            local_reference_b,
+            rest_rotation,
+            compliance: joint.compliance,
+            angular_compliance: joint.angular_compliance,
+            limits: joint
+                .has_limits()
+                .then_some((joint.lower_limit, joint.upper_limit)),
+            motor: if joint.has_motor() {
+                joint.motor
+            } else {
+                JointMotor::Disabled
+            },
+            motor_target: joint.motor_target,
+            motor_compliance: joint.motor_compliance,
+            motor_max_force: joint.motor_max_force,
+            break_force: joint.break_force,
+            break_torque: joint.break_torque,
+            position_lagrange: 0.0,
+            angular_lagrange: 0.0,
+            limit_lagrange: 0.0,
+            motor_lagrange: 0.0,
+            force: 0.0,
+            torque: 0.0,
+            broken: false,
+        }
+    }
+
+    /// Loads the unbroken joints of a world, skipping the ones referencing bodies that do not exist.
+    /// `bodies` must be sorted by id.
+    pub fn collect(
+        ctx: &ReducerContext,
+        world_id: PhysicsWorldId,
+        bodies: &[RigidBodyData],
+    ) -> Vec<Self> {
+        let exists = |id: u64| bodies.binary_search_by_key(&id, |b| b.id).is_ok();
+
+        Joint::all(ctx, world_id)
+            .filter(|joint| !joint.broken && joint.body_a != joint.body_b)
+            .filter(|joint| exists(joint.body_a) && exists(joint.body_b))
+            .map(|joint| JointConstraint::new(&joint))
+            .collect()
+    }
+
+    /// Lagrange multipliers are accumulated over the position iterations of a single substep.
+    pub fn reset_lagrange(&mut self) {
+        self.position_lagrange = 0.0;
+        self.angular_lagrange = 0.0;
+        self.limit_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
+    }
+
+    /// Converts the Lagrange multipliers of the anchors and of the angular part into a force
+    /// and a torque (f = lambda / h^2) and breaks the joint if either exceeds its threshold.
+    pub fn measure_force(&mut self, world: &PhysicsWorld, dt: f32) {
+        if self.broken {
+            return;
+        }
+
+        self.force = self.position_lagrange.abs() / dt.powi(2);
+        self.torque = self.angular_lagrange.abs() / dt.powi(2);
+        if self.force > self.break_force || self.torque > self.break_torque {
+            self.broken = true;
+
+            if world.debug_constraints() {
+                debug!(
+                    "[JointConstraint] joint {} broke, force: {}, break_force: {}, torque: {}, break_torque: {}",
+                    self.id, self.force, self.break_force, self.torque, self.break_torque
+                );
+            }
+        }
+    }
+
+    /// Persists the broken state of the joint, this is the only state written back by the solver.
+    pub fn update(&self, ctx: &ReducerContext) {
+        if !self.broken {
+            return;
+        }
+
+        if let Some(joint) = Joint::find(ctx, self.id) {
+            Joint {
+                broken: true,
+                broken_force: self.force,
+                broken_torque: self.torque,
+                ..joint
+            }
+            .update_from_simulation(ctx);
+        }
+    }
+
+    /// Moves the bodies so that `delta` (the error of anchor A relative to anchor B) goes to zero.
+    /// Returns the Lagrange multiplier update, clamped so that `|lagrange| <= max_lagrange`.
+    #[allow(clippy::too_many_arguments)]
+    fn solve_anchor(
+        &self,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        delta: Vec3,
+        ra: Vec3,
+        rb: Vec3,
+        lagrange: f32,
+        compliance: f32,
+        max_lagrange: f32,
+        dt: f32,
+    ) -> f32 {
+        let c = delta.length();
+        if c <= f32::EPSILON {
+            return 0.0;
+        }
+
+        let n = delta / c;
+        let wa = self.compute_generalized_inverse_mass(body_a, &ra, &n);
+        let wb = self.compute_generalized_inverse_mass(body_b, &rb, &n);
+
+        let delta_lagrange =
+            self.compute_lagrange_update(lagrange, c, &[n, -n], &[wa, wb], compliance, dt);
+        let delta_lagrange = clamp_lagrange(lagrange, delta_lagrange, max_lagrange);
+        self.apply_position_correction(body_a, body_b, delta_lagrange, &n, &ra, &rb);
+
+        delta_lagrange
+    }
+
+    /// Rotates the bodies so that `difference` (a rotation vector) goes to zero.
+    /// Body A is rotated along `-difference` and body B along `difference`.
+    /// Returns the Lagrange multiplier update, clamped so that `|lagrange| <= max_lagrange`.
+    #[allow(clippy::too_many_arguments)]
+    fn solve_alignment(
+        &self,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        difference: Vec3,
+        lagrange: f32,
+        compliance: f32,
+        max_lagrange: f32,
+        dt: f32,
+    ) -> f32 {
+        let angle = difference.length();
+        if angle <= f32::EPSILON {
+            return 0.0;
+        }
+
+        let axis = difference / angle;
+        let wa = self.compute_angular_generalized_inverse_mass(body_a, &axis);
+        let wb = self.compute_angular_generalized_inverse_mass(body_b, &axis);
+
+        let delta_lagrange = self.compute_lagrange_update(
+            lagrange,
+            angle,
+            &[axis, -axis],
+            &[wa, wb],
+            compliance,
+            dt,
+        );
+        let delta_lagrange = clamp_lagrange(lagrange, delta_lagrange, max_lagrange);
+        self.apply_angular_correction(body_a, body_b, delta_lagrange, &axis);
+
+        delta_lagrange
+    }
+
+    fn solve_fixed_rotation(
+        &mut self,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        dt: f32,
+    ) {
+        // Rotation that brings body B to its rest orientation relative to body A
+        let mut error = body_a.rotation() * self.rest_rotation * body_b.rotation().inverse();
+        if error.w < 0.0 {
+            error = error * -1.0;
+        }
+        self.angular_lagrange += self.solve_alignment(
+            body_a,
+            body_b,
+            2.0 * error.xyz(),
+            self.angular_lagrange,
+            self.angular_compliance,
+            f32::MAX,
+            dt,
+        );
+    }
+
+    fn solve_hinge_axis(
+        &mut self,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        dt: f32,
+    ) {
+        let axis_a = body_a.rotation().rotate(self.local_axis_a);
+        let axis_b = body_b.rotation().rotate(self.local_axis_b);
+        self.angular_lagrange += self.solve_alignment(
+            body_a,
+            body_b,
+            axis_b.cross(axis_a),
+            self.angular_lagrange,
+            self.angular_compliance,
+            f32::MAX,
+            dt,
+        );
+    }
+
+    fn solve_anchors(&mut self, body_a: &mut RigidBodyData, body_b: &mut RigidBodyData, dt: f32) {
+        let ra = body_a.lever_arm(self.local_anchor_a);
+        let rb = body_b.lever_arm(self.local_anchor_b);
+        let mut delta =
+            body_a.world_point(self.local_anchor_a) - body_b.world_point(self.local_anchor_b);
+
+        if self.joint_type == JointType::Prismatic {
+            // Free translation along the slide axis
+            let axis = body_a.rotation().rotate(self.local_axis_a);
+            delta = delta - axis * delta.dot(axis);
+        }
+
+        self.position_lagrange += self.solve_anchor(
+            body_a,
+            body_b,
+            delta,
+            ra,
+            rb,
+            self.position_lagrange,
+            self.compliance,
+            f32::MAX,
+            dt,
+        );
+    }
+
+    /// Returns the angle (revolute) or the distance along the axis (prismatic) of body B
+    /// relative to body A.
+    fn joint_coordinate(
+        &self,
+        position_a: Vec3,
+        rotation_a: Quat,
+        position_b: Vec3,
+        rotation_b: Quat,
+    ) -> f32 {
+        let axis = rotation_a.rotate(self.local_axis_a);
+
+        match self.joint_type {
+            JointType::Revolute => {
+                let reference_a = rotation_a.rotate(self.local_reference_a);
+                let reference_b = rotation_b.rotate(self.local_reference_b);
+                reference_a
+                    .cross(reference_b)
+                    .dot(axis)
+                    .atan2(reference_a.dot(reference_b))
+            }
+            _ => {
+                let anchor_a = position_a + rotation_a.rotate(self.local_anchor_a);
+                let anchor_b = position_b + rotation_b.rotate(self.local_anchor_b);
+                (anchor_b - anchor_a).dot(axis)
+            }
+        }
+    }
+
+    /// Moves the joint coordinate of body B relative to body A by `offset`.
+    #[allow(clippy::too_many_arguments)]
+    fn drive(
+        &self,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        offset: f32,
+        lagrange: f32,
+        compliance: f32,
+        max_lagrange: f32,
+        dt: f32,
+    ) -> f32 {
+        let axis = body_a.rotation().rotate(self.local_axis_a);
+
+        match self.joint_type {
+            JointType::Revolute => self.solve_alignment(
+                body_a,
+                body_b,
+                axis * offset,
+                lagrange,
+                compliance,
+                max_lagrange,
+                dt,
+            ),
+            _ => {
+                let ra = body_a.lever_arm(self.local_anchor_a);
+                let rb = body_b.lever_arm(self.local_anchor_b);
+                self.solve_anchor(
+                    body_a,
+                    body_b,
+                    axis * offset,
+                    ra,
+                    rb,
+                    lagrange,
+                    compliance,
+                    max_lagrange,
+                    dt,
+                )
+            }
+        }
+    }
+
+    fn solve_limits(&mut self, body_a: &mut RigidBodyData, body_b: &mut RigidBodyData, dt: f32) {
+        let Some((lower, upper)) = self.limits else {
+            return;
+        };
+
+        let coordinate = self.joint_coordinate(
+            body_a.position(),
+            body_a.rotation(),
+            body_b.position(),
+            body_b.rotation(),
+        );
+
+        let offset = if coordinate < lower {
+            lower - coordinate
+        } else if coordinate > upper {
+            upper - coordinate
+        } else {
+            return;
+        };
+
+        let compliance = self.limit_compliance();
+        self.limit_lagrange += self.drive(
+            body_a,
+            body_b,
+            offset,
+            self.limit_lagrange,
+            compliance,
+            f32::MAX,
+            dt,
+        );
+    }
+
+    fn solve_motor(&mut self, body_a: &mut RigidBodyData, body_b: &mut RigidBodyData, dt: f32) {
+        let target = match self.motor {
+            JointMotor::Disabled => return,
+            JointMotor::Position => self.motor_target,
+            JointMotor::Velocity => {
+                let start = self.joint_coordinate(
+                    body_a.previous_position(),
+                    body_a.previous_rotation(),
+                    body_b.previous_position(),
+                    body_b.previous_rotation(),
+                );
+                start + self.motor_target * dt
+            }
+        };
+
+        let coordinate = self.joint_coordinate(
+            body_a.position(),
+            body_a.rotation(),
+            body_b.position(),
+            body_b.rotation(),
+        );
+
+        let mut offset = target - coordinate;
+        if self.joint_type == JointType::Revolute {
+            offset = wrap_angle(offset);
+        }
+
+        // f = lambda / h^2
+        let max_lagrange = self.motor_max_force * dt.powi(2);
+        self.motor_lagrange += self.drive(
+            body_a,
+            body_b,
+            offset,
+            self.motor_lagrange,
+            self.motor_compliance,
+            max_lagrange,
+            dt,
+        );
+    }
+
+    fn limit_compliance(&self) -> f32 {
+        match self.joint_type {
+            JointType::Revolute => self.angular_compliance,
+            _ => self.compliance,
+        }
+    }
+}
+
+impl Constraint for JointConstraint {
+    fn solve(&mut self, world: &PhysicsWorld, bodies: &mut [RigidBodyData], dt: f32) {
+        if self.broken {
+            return;
+        }
+
+        let (body_a, body_b) = get_bodies_mut(self.a, self.b, bodies);
+        if !body_a.is_active() && !body_b.is_active() {
+            return;
+        }
+
+        match self.joint_type {
+            JointType::Fixed | JointType::Prismatic => {
+                self.solve_fixed_rotation(body_a, body_b, dt)
+            }
+            JointType::Revolute => self.solve_hinge_axis(body_a, body_b, dt),
+            JointType::Spherical => {}
+        }
+        self.solve_anchors(body_a, body_b, dt);
+        self.solve_motor(body_a, body_b, dt);
+        self.solve_limits(body_a, body_b, dt);
+
+        if world.debug_constraints() {
+            debug!(
+                "[JointConstraint] joint: {}, type: {:?}, a: {}, a_pos: {}, b: {}, b_pos: {}, position_lagrange: {}, angular_lagrange: {}, limit_lagrange: {}, motor_lagrange: {}",
+                self.id,
+                self.joint_type,
+                self.a,
+                body_a.position(),
+                self.b,
+                body_b.position(),
+                self.position_lagrange,
+                self.angular_lagrange,
+                self.limit_lagrange,
+                self.motor_lagrange
+            );
+        }
+    }
+}
+
+impl PositionConstraint for JointConstraint {}
+
+impl Display for JointConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "JointConstraint(id: {}, type: {:?}, a: {}, b: {}, compliance: {}, angular_compliance: {}, position_lagrange: {}, angular_lagrange: {}, force: {}, torque: {}, broken: {})",
+            self.id,
+            self.joint_type,
+            self.a,
+            self.b,
+            self.compliance,
+            self.angular_compliance,
+            self.position_lagrange,
+            self.angular_lagrange,
+            self.force,
+            self.torque,
+            self.broken
+        )
+    }
+}
+
+fn clamp_lagrange(lagrange: f32, delta_lagrange: f32, max_lagrange: f32) -> f32 {
+    (lagrange + delta_lagrange).clamp(-max_lagrange, max_lagrange) - lagrange
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+/// Returns a unit vector orthogonal to `axis`, used as the zero angle reference of revolute joints.
+fn any_orthogonal(axis: Vec3) -> Vec3 {
+    let other = if axis.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+    axis.cross(other).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sphere_body;
+
+    fn solve(constraint: &mut JointConstraint, bodies: &mut [RigidBodyData]) {
+        let world = PhysicsWorld::builder().build();
+        for _ in 0..50 {
+            constraint.reset_lagrange();
+            constraint.solve(&world, bodies, 1.0 / 60.0);
+        }
+    }
+
+    fn anchor_error(constraint: &JointConstraint, bodies: &[RigidBodyData]) -> f32 {
+        let anchor_a =
+            bodies[0].position() + bodies[0].rotation().rotate(constraint.local_anchor_a);
+        let anchor_b =
+            bodies[1].position() + bodies[1].rotation().rotate(constraint.local_anchor_b);
+        (anchor_a - anchor_b).length()
+    }
+
+    #[test]
+    fn spherical_joint_brings_the_anchors_together() {
+        let mut bodies = vec![
+            sphere_body(1, Vec3::ZERO),
+            sphere_body(2, Vec3::new(2.0, 1.0, 0.0)),
+        ];
+        let joint = Joint::builder()
+            .joint_type(JointType::Spherical)
+            .body_a(1)
+            .body_b(2)
+            .local_anchor_a(Vec3::new(0.5, 0.0, 0.0))
+            .local_anchor_b(Vec3::new(-0.5, 0.0, 0.0))
+            .build();
+        let mut constraint = JointConstraint::new(&joint);
+
+        solve(&mut constraint, &mut bodies);
+
+        assert!(anchor_error(&constraint, &bodies) < 1e-3);
+    }
+
+    #[test]
+    fn fixed_joint_restores_the_rest_rotation() {
+        let mut bodies = vec![
+            sphere_body(1, Vec3::ZERO),
+            sphere_body(2, Vec3::new(1.0, 0.0, 0.0)),
+        ];
+        bodies[1].set_rotation(Quat::from_axis_angle(Vec3::Z, 0.5));
+        let joint = Joint::builder()
+            .joint_type(JointType::Fixed)
+            .body_a(1)
+            .body_b(2)
+            .local_anchor_a(Vec3::new(0.5, 0.0, 0.0))
+            .local_anchor_b(Vec3::new(-0.5, 0.0, 0.0))
+            .build();
+        let mut constraint = JointConstraint::new(&joint);
+
+        solve(&mut constraint, &mut bodies);
+
+        let relative = bodies[0].rotation().inverse() * bodies[1].rotation();
+        assert!(anchor_error(&constraint, &bodies) < 1e-3);
+        assert!(relative.w.abs() > 1.0 - 1e-4);
+    }
+}
@@ -1,6 +1,10 @@
+mod distance;
+mod joint;
 mod penetration;
 mod position;
 
+pub use distance::*;
+pub use joint::*;
 pub use penetration::*;
 pub use position::*;
 
@@ -25,7 +29,7 @@ pub(crate) trait Constraint {
             .enumerate()
             .fold(0.0, |acc, (i, w)| acc + w * gradients[i].length_squared());
 
-        if w_sum == f32::EPSILON {
+        if w_sum <= f32::EPSILON {
             return 0.0;
         }
 
@@ -2,7 +2,9 @@ use std::fmt::Display;
 
 use log::debug;
 
-use crate::{math::Vec3, utils::get_bodies_mut, CollisionPoint, PhysicsWorld, RigidBodyData};
+use crate::{
+    math::Vec3, utils::get_bodies_mut, CollisionPoint, ContactData, PhysicsWorld, RigidBodyData,
+};
 
 use super::{position::PositionConstraint, Constraint};
 
@@ -15,6 +17,7 @@ pub struct PenetrationConstraint {
     pub local_a: Vec3,
     pub local_b: Vec3,
     pub normal: Vec3,
+    /// Positive when the bodies are penetrating, updated every time the constraint is solved.
     pub penetration_depth: f32,
     pub compliance: f32,
     pub normal_lagrange: f32,
@@ -22,6 +25,10 @@ pub struct PenetrationConstraint {
     pub tangential_lagrange: f32,
     pub static_friction_force: Vec3,
     pub tangent_lagrange: f32,
+    /// The index of the manifold this contact comes from in the pair's cached manifolds.
+    pub manifold_index: usize,
+    /// The index of this contact in its manifold.
+    pub point_index: usize,
 }
 
 impl PenetrationConstraint {
@@ -39,16 +46,65 @@ impl PenetrationConstraint {
             local_a: point.local_a,
             local_b: point.local_b,
             normal: point.normal,
-            penetration_depth: point.distance,
+            penetration_depth: -point.distance,
             compliance,
             normal_lagrange: 0.0,
             normal_force: Vec3::ZERO,
             tangential_lagrange: 0.0,
             static_friction_force: Vec3::ZERO,
             tangent_lagrange: 0.0,
+            manifold_index: 0,
+            point_index: 0,
         }
     }
 
+    /// Links the constraint to its manifold point and starts from the multiplier accumulated by
+    /// the same contact during the previous substep, scaled by `coefficient`.
+    pub fn with_warm_start(
+        mut self,
+        manifold_index: usize,
+        point_index: usize,
+        data: &ContactData,
+        coefficient: f32,
+    ) -> Self {
+        self.manifold_index = manifold_index;
+        self.point_index = point_index;
+        self.normal_lagrange = data.normal_lagrange * coefficient;
+        self
+    }
+
+    /// Returns the contact points relative to the center of mass of each body, in world space.
+    pub fn lever_arms(&self, body_a: &RigidBodyData, body_b: &RigidBodyData) -> (Vec3, Vec3) {
+        (
+            body_a.lever_arm(self.local_a),
+            body_b.lever_arm(self.local_b),
+        )
+    }
+
+    /// Applies the correction matching the warm started multiplier, so that the solver only
+    /// has to resolve what changed since the previous substep.
+    pub fn warm_start(&mut self, bodies: &mut [RigidBodyData]) {
+        if self.normal_lagrange.abs() <= f32::EPSILON {
+            return;
+        }
+
+        let (body_a, body_b) = get_bodies_mut(self.a, self.b, bodies);
+        let (ra, rb) = self.lever_arms(body_a, body_b);
+        self.apply_position_correction(
+            body_a,
+            body_b,
+            self.normal_lagrange,
+            &self.normal,
+            &ra,
+            &rb,
+        );
+    }
+
+    /// Returns true if the contact pushed the bodies apart during the last substep.
+    pub fn is_touching(&self) -> bool {
+        self.normal_lagrange.abs() > f32::EPSILON
+    }
+
     fn solve_contact(
         &mut self,
         world: &PhysicsWorld,
@@ -56,16 +112,19 @@ impl PenetrationConstraint {
         body_b: &mut RigidBodyData,
         dt: f32,
     ) {
+        let (ra, rb) = self.lever_arms(body_a, body_b);
+        self.world_a = body_a.world_point(self.local_a);
+        self.world_b = body_b.world_point(self.local_b);
+        self.penetration_depth = (self.world_a - self.world_b).dot(self.normal);
+
         // Shorter aliases for readability
         let penetraion = self.penetration_depth;
         let normal = self.normal;
         let compliance = self.compliance;
         let lagrange = self.normal_lagrange;
 
-        let ra = body_a.rotation().rotate(self.local_a);
-        let rb = body_b.rotation().rotate(self.local_b);
-
-        if penetraion >= 0.0 {
+        // A separated contact only needs to be solved to remove a warm started correction
+        if penetraion <= 0.0 && lagrange >= 0.0 {
             return;
         }
 
@@ -77,6 +136,9 @@ impl PenetrationConstraint {
 
         let delta_lagrange =
             self.compute_lagrange_update(lagrange, penetraion, &gradients, &w, compliance, dt);
+        // Contacts can only push the bodies apart, the accumulated multiplier (negative, as the
+        // normal points from A to B) is clamped so that it never pulls them together
+        let delta_lagrange = (lagrange + delta_lagrange).min(0.0) - lagrange;
         self.normal_lagrange += delta_lagrange;
         self.normal_force = self.normal_lagrange * normal / dt.powi(2);
 
@@ -106,13 +168,12 @@ impl PenetrationConstraint {
         let penetration = self.penetration_depth;
         let normal = self.normal;
         let compliance = self.compliance;
-        let lagrange = self.tangential_lagrange;
-        let r1 = self.world_a;
-        let r2 = self.world_b;
+        let lagrange = self.tangent_lagrange;
+        let (r1, r2) = self.lever_arms(body1, body2);
 
         // Compute contact positions at the current state and before substep integration
-        let p1 = body1.position() + body1.rotation().rotate(self.local_a);
-        let p2 = body2.position() + body2.rotation().rotate(self.local_b);
+        let p1 = body1.world_point(self.local_a);
+        let p2 = body2.world_point(self.local_b);
         let prev_p1 = body1.previous_position() + body1.previous_rotation().rotate(self.local_a);
         let prev_p2 = body2.previous_position() + body2.previous_rotation().rotate(self.local_b);
 
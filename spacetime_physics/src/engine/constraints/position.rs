@@ -49,12 +49,52 @@ pub trait PositionConstraint: Constraint {
 
         // Proper quaternion integration for angular correction
         let dq = Quat::from_scaled_axis(delta_angle);
-        body.set_rotation(dq * body.rotation());
+        body.set_rotation_around_center_of_mass(dq * body.rotation());
+    }
+
+    fn apply_angular_correction(
+        &self,
+        body_a: &mut RigidBodyData,
+        body_b: &mut RigidBodyData,
+        delta_lagrange: f32,
+        axis: &Vec3,
+    ) {
+        if delta_lagrange.abs() < f32::EPSILON {
+            return;
+        }
+
+        let p = delta_lagrange * axis;
+
+        if body_a.is_dynamic() {
+            Self::apply_body_angular_correction(body_a, &p, 1.0);
+        }
+
+        if body_b.is_dynamic() {
+            Self::apply_body_angular_correction(body_b, &p, -1.0);
+        }
+    }
+
+    fn apply_body_angular_correction(body: &mut RigidBodyData, p: &Vec3, sign: f32) {
+        let delta_angle = sign * body.effective_inverse_inertia() * *p;
+        let dq = Quat::from_scaled_axis(delta_angle);
+        body.set_rotation_around_center_of_mass(dq * body.rotation());
     }
 
     fn compute_generalized_inverse_mass(&self, body: &RigidBodyData, r: &Vec3, n: &Vec3) -> f32 {
+        if !body.is_dynamic() {
+            return 0.0;
+        }
+
         let inv_inertia = body.effective_inverse_inertia();
         let r_cross_n = r.cross(n);
-        body.inv_mass() + r_cross_n.dot(inv_inertia * r_cross_n)
+        n.dot(*n * body.effective_inverse_mass()) + r_cross_n.dot(inv_inertia * r_cross_n)
+    }
+
+    fn compute_angular_generalized_inverse_mass(&self, body: &RigidBodyData, axis: &Vec3) -> f32 {
+        if !body.is_dynamic() {
+            return 0.0;
+        }
+
+        axis.dot(body.effective_inverse_inertia() * *axis)
     }
 }
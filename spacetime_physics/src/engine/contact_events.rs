@@ -0,0 +1,153 @@
+use std::collections::{HashMap, HashSet};
+
+use log::debug;
+use spacetimedb::ReducerContext;
+
+use crate::{
+    math::Vec3, CollisionEvent, CollisionEventKind, PhysicsWorld, RigidBodyData, RigidBodyId,
+};
+
+use super::constraints::PenetrationConstraint;
+
+#[derive(Debug, Clone, Copy)]
+struct ContactSummary {
+    point: Vec3,
+    normal: Vec3,
+    impulse: f32,
+    max_lagrange: f32,
+}
+
+/// Accumulates the contacts solved during the substeps of a step and turns them into
+/// [`CollisionEvent`]s by comparing them with the events of the previous step.
+pub(crate) struct ContactEvents {
+    contacts: HashMap<(RigidBodyId, RigidBodyId), ContactSummary>,
+}
+
+impl ContactEvents {
+    pub fn new() -> Self {
+        Self {
+            contacts: HashMap::new(),
+        }
+    }
+
+    /// Records the contacts that pushed their bodies apart during a substep.
+    pub fn record(&mut self, world: &PhysicsWorld, constraints: &[PenetrationConstraint], dt: f32) {
+        if !world.collision_events {
+            return;
+        }
+
+        for constraint in constraints.iter().filter(|c| c.is_touching()) {
+            let lagrange = constraint.normal_lagrange.abs();
+            let point = (constraint.world_a + constraint.world_b) * 0.5;
+            let summary =
+                self.contacts
+                    .entry((constraint.a, constraint.b))
+                    .or_insert(ContactSummary {
+                        point,
+                        normal: constraint.normal,
+                        impulse: 0.0,
+                        max_lagrange: 0.0,
+                    });
+
+            // impulse = f * h = λ / h
+            summary.impulse += lagrange / dt;
+            if lagrange > summary.max_lagrange {
+                summary.max_lagrange = lagrange;
+                summary.point = point;
+                summary.normal = constraint.normal;
+            }
+        }
+    }
+
+    /// Updates the collision events of the world with the contacts of this step. Pairs that
+    /// were touching during the previous step are persisted if they still are, or if both bodies
+    /// went to sleep since sleeping bodies are not solved, and ended otherwise. Only the events
+    /// whose data changed are written, so resting and sleeping pairs cost nothing.
+    pub fn write(&self, ctx: &ReducerContext, world: &PhysicsWorld, bodies: &[RigidBodyData]) {
+        if !world.collision_events {
+            return;
+        }
+
+        let sw = world.stopwatch("collision_events");
+        let is_resting = |id: RigidBodyId| {
+            bodies
+                .binary_search_by_key(&id, |b| b.id)
+                .is_ok_and(|index| !bodies[index].is_active())
+        };
+
+        let mut previous = HashSet::new();
+        for event in CollisionEvent::all(ctx, world.id) {
+            if !event.is_touching() {
+                event.delete(ctx);
+                continue;
+            }
+
+            let pair = (event.body_a, event.body_b);
+            previous.insert(pair);
+            let persisted = if let Some(contact) = self.contacts.get(&pair) {
+                CollisionEvent {
+                    kind: CollisionEventKind::Persisted,
+                    point: contact.point,
+                    normal: contact.normal,
+                    impulse: contact.impulse,
+                    ..event
+                }
+            } else if is_resting(event.body_a) && is_resting(event.body_b) {
+                CollisionEvent {
+                    kind: CollisionEventKind::Persisted,
+                    impulse: 0.0,
+                    ..event
+                }
+            } else {
+                event.delete(ctx);
+                Self::insert(
+                    ctx,
+                    world,
+                    CollisionEvent {
+                        id: 0,
+                        kind: CollisionEventKind::Ended,
+                        impulse: 0.0,
+                        ..event
+                    },
+                );
+                continue;
+            };
+
+            if persisted != event {
+                Self::log(world, &persisted.update(ctx));
+            }
+        }
+
+        for (&(body_a, body_b), contact) in &self.contacts {
+            if previous.contains(&(body_a, body_b)) {
+                continue;
+            }
+
+            Self::insert(
+                ctx,
+                world,
+                CollisionEvent {
+                    id: 0,
+                    world_id: world.id,
+                    kind: CollisionEventKind::Started,
+                    body_a,
+                    body_b,
+                    point: contact.point,
+                    normal: contact.normal,
+                    impulse: contact.impulse,
+                },
+            );
+        }
+        sw.end();
+    }
+
+    fn insert(ctx: &ReducerContext, world: &PhysicsWorld, event: CollisionEvent) {
+        Self::log(world, &event.insert(ctx));
+    }
+
+    fn log(world: &PhysicsWorld, event: &CollisionEvent) {
+        if world.debug_narrow_phase() {
+            debug!("[PhysicsWorld#{}] [CollisionEvent] {}", world.id, event);
+        }
+    }
+}
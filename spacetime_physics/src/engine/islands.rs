@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use log::debug;
+
+use crate::PhysicsWorld;
+
+use super::{
+    collision_detection::Collidable,
+    constraints::{DistanceConstraint, JointConstraint},
+    RigidBodyData,
+};
+
+/// Groups the dynamic bodies that are touching each other or connected by a joint into
+/// simulation islands. An island is either entirely awake or entirely asleep, a body can only
+/// fall asleep once every body of its island has been resting for long enough, and waking up a
+/// single body wakes up its whole island.
+pub(crate) struct Islands {
+    parents: Vec<usize>,
+    /// Pairs of body indices that are touching or connected by a joint.
+    edges: Vec<(usize, usize)>,
+}
+
+impl Islands {
+    pub fn build(
+        bodies: &[RigidBodyData],
+        pairs: &HashSet<(Collidable, Collidable)>,
+        joints: &[JointConstraint],
+        distance_joints: &[DistanceConstraint],
+    ) -> Self {
+        let index_of = |id: u64| bodies.binary_search_by_key(&id, |b| b.id).ok();
+
+        let mut edges: Vec<(usize, usize)> = pairs
+            .iter()
+            .filter(|(a, b)| !a.is_trigger && !b.is_trigger)
+            .map(|(a, b)| (a.rigidbody_index, b.rigidbody_index))
+            .collect();
+        edges.extend(
+            joints
+                .iter()
+                .filter(|joint| !joint.broken)
+                .filter_map(|joint| Some((index_of(joint.a)?, index_of(joint.b)?))),
+        );
+        edges.extend(
+            distance_joints
+                .iter()
+                .filter_map(|joint| Some((index_of(joint.a)?, index_of(joint.b?)?))),
+        );
+
+        let mut islands = Self {
+            parents: (0..bodies.len()).collect(),
+            edges,
+        };
+
+        // Static and kinematic bodies do not propagate islands, otherwise every body resting on
+        // the ground would end up in the same island.
+        for i in 0..islands.edges.len() {
+            let (a, b) = islands.edges[i];
+            if bodies[a].is_dynamic() && bodies[b].is_dynamic() {
+                islands.union(a, b);
+            }
+        }
+
+        islands
+    }
+
+    /// Wakes up the islands containing an awake body, a sleeping body that has been written to
+    /// (non-zero velocity, force or torque) or a body touched by a moving kinematic body.
+    /// When sleeping is disabled on the world, every body is woken up.
+    pub fn wake_up(&mut self, world: &PhysicsWorld, bodies: &mut [RigidBodyData]) {
+        let sw = world.stopwatch("wake_up_islands");
+        let mut awake = vec![!world.sleeping_enabled; bodies.len()];
+
+        for (i, body) in bodies.iter().enumerate() {
+            if body.is_dynamic() && (!body.is_sleeping() || body.has_pending_motion()) {
+                let root = self.find(i);
+                awake[root] = true;
+            }
+        }
+
+        for i in 0..self.edges.len() {
+            let (a, b) = self.edges[i];
+            for (kinematic, other) in [(a, b), (b, a)] {
+                if bodies[kinematic].is_kinematic()
+                    && bodies[kinematic].has_moved()
+                    && bodies[other].is_dynamic()
+                {
+                    let root = self.find(other);
+                    awake[root] = true;
+                }
+            }
+        }
+
+        for (i, body) in bodies.iter_mut().enumerate() {
+            let root = self.find(i);
+            if awake[root] && body.is_sleeping() {
+                body.wake_up();
+
+                if world.debug {
+                    debug!("[Islands] body {} woke up", body.id);
+                }
+            }
+        }
+        sw.end();
+    }
+
+    /// Accumulates the time each awake body has spent below the sleep thresholds and puts to
+    /// sleep the islands in which every body has been resting for at least `time_to_sleep`.
+    pub fn update_sleep(&mut self, world: &PhysicsWorld, bodies: &mut [RigidBodyData]) {
+        if !world.sleeping_enabled {
+            return;
+        }
+
+        let sw = world.stopwatch("update_sleep");
+        let mut can_sleep = vec![true; bodies.len()];
+
+        for (i, body) in bodies.iter_mut().enumerate() {
+            if !body.is_active() {
+                continue;
+            }
+
+            if body.linear_velocity().length() < world.sleep_linear_threshold
+                && body.angular_velocity().length() < world.sleep_angular_threshold
+            {
+                body.set_sleep_timer(body.sleep_timer() + world.time_step);
+            } else {
+                body.set_sleep_timer(0.0);
+            }
+
+            if body.sleep_timer() < world.time_to_sleep {
+                let root = self.find(i);
+                can_sleep[root] = false;
+            }
+        }
+
+        for (i, body) in bodies.iter_mut().enumerate() {
+            let root = self.find(i);
+            if can_sleep[root] && body.is_active() {
+                body.sleep();
+
+                if world.debug {
+                    debug!("[Islands] body {} fell asleep", body.id);
+                }
+            }
+        }
+        sw.end();
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            // Path halving, keeps the trees flat without recursion
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a != root_b {
+            self.parents[root_b] = root_a;
+        }
+    }
+}
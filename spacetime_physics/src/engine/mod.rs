@@ -1,10 +1,16 @@
 use std::collections::HashMap;
 
 use collision_detection::CollisionDetection;
+use constraints::{DistanceConstraint, JointConstraint};
+use contact_events::ContactEvents;
+use islands::Islands;
 use log::debug;
+use shape_cast_data::ShapeCastData;
 use spacetimedb::ReducerContext;
-use trigger_data::TriggerData;
-use xpbd::{integrate_bodies, recompute_velocities, solve_constraints, solve_velocities};
+use xpbd::{
+    integrate_bodies, recompute_velocities, solve_constraints, solve_velocities,
+    warm_start_constraints,
+};
 
 use crate::{
     math::{Quat, Vec3},
@@ -12,13 +18,23 @@ use crate::{
     Collider, RayCast,
 };
 
+mod broad_phase;
 mod collision_detection;
 mod constraints;
+mod contact_events;
+mod islands;
 mod rigid_body_data;
+mod shape_cache;
+mod shape_cast_data;
 mod trigger_data;
+mod world_cache;
 mod xpbd;
 
+pub(crate) use broad_phase::BroadPhase;
 pub use rigid_body_data::RigidBodyData;
+pub(crate) use shape_cache::ShapeCache;
+pub(crate) use trigger_data::TriggerData;
+pub(crate) use world_cache::WorldCache;
 
 pub type KinematicBody = (u64, (Vec3, Quat));
 
@@ -29,24 +45,43 @@ pub fn step_world(
 ) {
     let sw = world.stopwatch("step_world");
 
+    let mut cache = WorldCache::take(world);
+
     let load_sw = world.stopwatch("load_data");
     let colliders = Collider::all(ctx, world.id);
-    let mut triggers = TriggerData::collect(ctx, world.id, &colliders);
-    let mut entities = RigidBodyData::collect(ctx, world.id, &colliders);
+    let mut triggers = TriggerData::collect(ctx, world.id, &colliders, &mut cache.shapes);
+    let mut entities =
+        RigidBodyData::collect_with_shapes(ctx, world.id, &colliders, &mut cache.shapes);
+    cache.shapes.retain_used();
     let mut raycasts = RayCast::all(ctx, world.id);
+    let mut shapecasts = ShapeCastData::collect(ctx, world.id, &colliders);
+    let mut joints = JointConstraint::collect(ctx, world.id, &entities);
+    let mut distance_joints = DistanceConstraint::collect(ctx, world.id, &entities);
 
     let entities = entities.as_mut_slice();
     let triggers = triggers.as_mut_slice();
     let raycasts = raycasts.as_mut_slice();
+    let shapecasts = shapecasts.as_mut_slice();
+    let joints = joints.as_mut_slice();
+    let distance_joints = distance_joints.as_mut_slice();
     load_sw.end();
 
     let dt = world.time_step / world.sub_step as f32;
+    let mut contact_events = ContactEvents::new();
 
+    cache.restore_unwritten_bodies(entities);
     sync_kinematic_bodies(kinematic_entities, entities);
 
     // TODO: Include triggers in the entities list
     let mut collision_detection = CollisionDetection::new();
-    collision_detection.broad_phase(world, entities, triggers, raycasts);
+    collision_detection.broad_phase(
+        world,
+        entities,
+        triggers,
+        raycasts,
+        shapecasts,
+        &mut cache.broad_phase,
+    );
 
     if world.debug_broad_phase() {
         debug!(
@@ -56,6 +91,14 @@ pub fn step_world(
         );
     }
 
+    let mut islands = Islands::build(
+        entities,
+        collision_detection.broad_phase_pairs(),
+        joints,
+        distance_joints,
+    );
+    islands.wake_up(world, entities);
+
     for i in 0..world.sub_step {
         let sw = world.stopwatch(&format!("substep_{}", i));
         if world.debug_substep() {
@@ -64,7 +107,7 @@ pub fn step_world(
 
         // TODO: Ignore trigger bodies in the narrow phase
         let mut penetration_constraints =
-            collision_detection.narrow_phase_constraints(world, entities);
+            collision_detection.narrow_phase_constraints(world, entities, &mut cache);
         let penetration_constraints = penetration_constraints.as_mut_slice();
 
         if world.debug_substep() {
@@ -72,13 +115,31 @@ pub fn step_world(
         }
 
         integrate_bodies(entities, world, dt);
+        collision_detection.continuous_collisions(world, entities);
+        warm_start_constraints(world, penetration_constraints, entities);
 
+        joints.iter_mut().for_each(JointConstraint::reset_lagrange);
+        distance_joints
+            .iter_mut()
+            .for_each(DistanceConstraint::reset_lagrange);
         for _ in 0..world.position_iterations {
-            solve_constraints(world, penetration_constraints, entities, dt);
+            solve_constraints(
+                world,
+                penetration_constraints,
+                joints,
+                distance_joints,
+                entities,
+                dt,
+            );
         }
+        joints
+            .iter_mut()
+            .for_each(|joint| joint.measure_force(world, dt));
 
         recompute_velocities(world, entities, dt);
         solve_velocities(world, penetration_constraints, entities, dt);
+        cache.store_contact_lagrange(penetration_constraints);
+        contact_events.record(world, penetration_constraints, dt);
 
         if world.debug {
             debug_bodies(entities);
@@ -89,13 +150,33 @@ pub fn step_world(
 
     collision_detection.narrow_phase_triggers(ctx, world, entities, triggers);
     collision_detection.narrow_phase_raycast(ctx, world, entities, raycasts);
+    collision_detection.narrow_phase_shapecast(ctx, world, entities, shapecasts);
+
+    for joint in joints.iter() {
+        joint.update(ctx);
+    }
+
+    // Refit the broad phase with the final poses before the bodies fall asleep, so that the
+    // instant queries running before the next step use up to date bounding boxes
+    cache.broad_phase.refit_moved(world, entities);
+    islands.update_sleep(world, entities);
+    contact_events.write(ctx, world, entities);
+
+    cache.retain_contacts(collision_detection.broad_phase_pairs());
 
     if world.debug {
         debug!("---------- End of substeps ----------");
     }
 
     let update_sw = world.stopwatch("update_bodies");
-    for entity in entities {
+    for entity in entities.iter() {
+        if !entity.needs_update(world) {
+            if entity.has_unwritten_changes() {
+                cache.store_unwritten_body(entity);
+            }
+            continue;
+        }
+
         if world.debug {
             debug!(
                 "Updating {} position: {} -> {}, velocity: {}, rotation: {}",
@@ -110,6 +191,13 @@ pub fn step_world(
     }
     update_sw.end();
 
+    let world = PhysicsWorld {
+        step_count: world.step_count + 1,
+        ..*world
+    }
+    .update(ctx);
+    cache.store(&world);
+
     if world.debug {
         debug!("-------------------------------------------------------------");
     }
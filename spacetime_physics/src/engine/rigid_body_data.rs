@@ -1,13 +1,16 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
 use parry3d::na::Isometry3;
 use spacetimedb::ReducerContext;
 
 use crate::{
     math::{Mat3, Quat, Vec3},
-    Collider, ColliderId, PhysicsWorldId, RigidBody, RigidBodyProperties, ShapeWrapper,
+    Collider, ColliderId, CollisionGroups, ComputedMassProperties, LockedAxes, PhysicsWorld,
+    PhysicsWorldId, RigidBody, RigidBodyId, RigidBodyProperties, RigidBodyType, ShapeWrapper,
 };
 
+use super::shape_cache::ShapeCache;
+
 /// Represents a rigid body in the physics engine, containing its properties and state.
 /// This struct is used as an abstraction layer to the RigidBody storage in the database,
 /// it's also used to store properties exclusive to the physics engine algorithms,
@@ -15,18 +18,23 @@ use crate::{
 pub struct RigidBodyData {
     pub id: u64,
     rb: RigidBody,
-    mass: f32,
     inv_mass: f32,
     friction_static_coefficient: f32,
     friction_dynamic_coefficient: f32,
     restitution_coefficient: f32,
-    shape: ShapeWrapper,
-    inertia_tensor: Mat3,
+    linear_damping: f32,
+    angular_damping: f32,
+    locked_axes: LockedAxes,
+    shape: Rc<ShapeWrapper>,
     inv_inertia_tensor: Mat3,
     pre_solve_linear_velocity: Vec3,
     pre_solve_angular_velocity: Vec3,
     previous_position: Vec3,
     previous_rotation: Quat,
+    /// The body as it is in the database, used to only write back meaningful changes.
+    stored: RigidBody,
+    /// Set when continuous collision detection clamped the motion of the body during the substep.
+    ccd_impact: bool,
     is_dirty: bool,
 }
 
@@ -35,42 +43,136 @@ impl RigidBodyData {
         rigid_body: RigidBody,
         rb_properties: &RigidBodyProperties,
         collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+    ) -> Self {
+        Self::with_shapes(
+            rigid_body,
+            rb_properties,
+            collider,
+            colliders,
+            &mut ShapeCache::default(),
+        )
+    }
+
+    /// Same as [`RigidBodyData::new`], reusing the shape of the collider from `shapes` when it
+    /// has not changed since it was built. Static bodies never move, their mass properties are
+    /// not computed.
+    pub(crate) fn with_shapes(
+        rigid_body: RigidBody,
+        rb_properties: &RigidBodyProperties,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+        shapes: &mut ShapeCache,
     ) -> Self {
-        let inertia_tensor = collider.inertia_tensor(rb_properties.mass);
+        let shape = shapes.shape(collider, colliders, rigid_body.scale);
+        let mut rigid_body = rigid_body;
+        if rigid_body.body_type != RigidBodyType::Static {
+            let unit_mass_properties =
+                shapes.unit_mass_properties(collider, colliders, rigid_body.scale);
+            rigid_body.mass_properties =
+                Self::compute_mass_properties(&rigid_body, rb_properties, unit_mass_properties);
+        } else {
+            rigid_body.mass_properties = ComputedMassProperties::default();
+        }
+        let mass_properties = rigid_body.mass_properties;
+
         Self {
             id: rigid_body.id,
             rb: rigid_body,
-            shape: ShapeWrapper::from(collider),
-            mass: rb_properties.mass,
-            inv_mass: rb_properties.inv_mass,
+            shape,
+            inv_mass: if mass_properties.mass > 0.0 {
+                1.0 / mass_properties.mass
+            } else {
+                0.0
+            },
             friction_static_coefficient: rb_properties.friction_static_coefficient,
             friction_dynamic_coefficient: rb_properties.friction_dynamic_coefficient,
             restitution_coefficient: rb_properties.restitution_coefficient,
-            inertia_tensor,
-            inv_inertia_tensor: inertia_tensor.inverse(),
+            linear_damping: rb_properties.linear_damping,
+            angular_damping: rb_properties.angular_damping,
+            locked_axes: rb_properties.locked_axes,
+            // Bodies without mass (static bodies and shapes without volume) get a zero inverse
+            // inertia, which prevents them from rotating instead of spinning infinitely fast
+            inv_inertia_tensor: if mass_properties.mass > 0.0 {
+                mass_properties.inertia_tensor.inverse()
+            } else {
+                Mat3::ZERO
+            },
             pre_solve_linear_velocity: rigid_body.linear_velocity,
             pre_solve_angular_velocity: rigid_body.angular_velocity,
             previous_position: rigid_body.position,
             previous_rotation: rigid_body.rotation,
+            stored: rigid_body,
+            ccd_impact: false,
             is_dirty: false,
         }
     }
 
+    /// Computes the mass properties of a body in its local space from the mass properties of
+    /// its collider for a density of 1.0, using either the mass or the density of its properties.
+    fn compute_mass_properties(
+        rigid_body: &RigidBody,
+        rb_properties: &RigidBodyProperties,
+        shape_properties: ComputedMassProperties,
+    ) -> ComputedMassProperties {
+        let mass = match rb_properties.density {
+            Some(density) => shape_properties.mass * density,
+            None => rb_properties.mass,
+        };
+
+        // The mass and the inertia scale linearly with the density for a given shape
+        let inertia_scale = if shape_properties.mass > 0.0 {
+            mass / shape_properties.mass
+        } else {
+            0.0
+        };
+
+        // Mass properties are computed in the collider space, move them to the body space
+        let collider_rotation = rigid_body.collider_rotation.to_mat3();
+        let inertia_tensor = collider_rotation
+            * (inertia_scale * shape_properties.inertia_tensor)
+            * collider_rotation.transpose();
+        let center_of_mass = rb_properties.center_of_mass.unwrap_or(
+            rigid_body.collider_position
+                + rigid_body
+                    .collider_rotation
+                    .rotate(shape_properties.center_of_mass),
+        );
+
+        ComputedMassProperties {
+            mass,
+            center_of_mass,
+            inertia_tensor,
+        }
+    }
+
     pub fn collect(
         ctx: &ReducerContext,
         world_id: PhysicsWorldId,
         colliders: &HashMap<ColliderId, Collider>,
+    ) -> Vec<Self> {
+        Self::collect_with_shapes(ctx, world_id, colliders, &mut ShapeCache::default())
+    }
+
+    /// Same as [`RigidBodyData::collect`], reusing the shapes of `shapes`.
+    pub(crate) fn collect_with_shapes(
+        ctx: &ReducerContext,
+        world_id: PhysicsWorldId,
+        colliders: &HashMap<ColliderId, Collider>,
+        shapes: &mut ShapeCache,
     ) -> Vec<Self> {
         let rb_properties = RigidBodyProperties::all(ctx, world_id)
             .map(|props| (props.id, props))
             .collect::<HashMap<_, _>>();
 
         let mut entities: Vec<_> = RigidBody::all(ctx, world_id)
-            .map(move |rb| {
-                RigidBodyData::new(
+            .map(|rb| {
+                RigidBodyData::with_shapes(
                     rb,
                     rb_properties.get(&rb.properties_id).unwrap(),
                     colliders.get(&rb.collider_id).unwrap(),
+                    colliders,
+                    shapes,
                 )
             })
             .collect();
@@ -80,19 +182,43 @@ impl RigidBodyData {
         entities
     }
 
+    /// Loads a single body of a world, `None` if it does not exist or belongs to another world.
+    pub fn find(
+        ctx: &ReducerContext,
+        world_id: PhysicsWorldId,
+        id: RigidBodyId,
+        colliders: &HashMap<ColliderId, Collider>,
+    ) -> Option<Self> {
+        let rb = RigidBody::find(ctx, id).filter(|rb| rb.world_id == world_id)?;
+        let rb_properties = RigidBodyProperties::find(ctx, rb.properties_id)?;
+        let collider = colliders.get(&rb.collider_id)?;
+        Some(RigidBodyData::new(rb, &rb_properties, collider, colliders))
+    }
+
     pub fn effective_mass(&self) -> Vec3 {
-        Vec3::splat(self.mass)
+        Vec3::splat(self.rb.mass_properties.mass)
     }
 
     pub fn effective_inverse_mass(&self) -> Vec3 {
-        // TODO: Take into account locked axes
-        Vec3::splat(self.inv_mass)
+        Vec3::splat(self.inv_mass) * self.locked_axes.translation_mask()
     }
 
+    /// Returns the inverse inertia tensor in world space, locked rotation axes are removed by
+    /// zeroing their rows and columns so that no correction can rotate the body around them.
     pub fn effective_inverse_inertia(&self) -> Mat3 {
-        // TODO: Take into account locked axes
         let r = self.rb.rotation.to_mat3();
-        r * self.inv_inertia_tensor * r.transpose()
+        let inv_inertia = r * self.inv_inertia_tensor * r.transpose();
+
+        if !self.locked_axes.is_rotation_locked() {
+            return inv_inertia;
+        }
+
+        let mask = Mat3::from_diagonal(self.locked_axes.rotation_mask());
+        mask * inv_inertia * mask
+    }
+
+    pub fn locked_axes(&self) -> LockedAxes {
+        self.locked_axes
     }
 
     pub fn combine_static_friction(&self, other: &Self) -> f32 {
@@ -107,6 +233,14 @@ impl RigidBodyData {
         (self.restitution_coefficient + other.restitution_coefficient) / 2.0
     }
 
+    pub fn linear_damping(&self) -> f32 {
+        self.linear_damping
+    }
+
+    pub fn angular_damping(&self) -> f32 {
+        self.angular_damping
+    }
+
     pub fn previous_position(&self) -> Vec3 {
         self.previous_position
     }
@@ -131,6 +265,121 @@ impl RigidBodyData {
         self.rb.is_kinematic()
     }
 
+    pub fn is_sleeping(&self) -> bool {
+        self.rb.sleeping
+    }
+
+    /// Returns true for dynamic bodies that are not sleeping, the only ones being simulated.
+    pub fn is_active(&self) -> bool {
+        self.is_dynamic() && !self.is_sleeping()
+    }
+
+    /// Returns true if a velocity, force or torque has been applied to the body, which means
+    /// it has to be woken up.
+    pub fn has_pending_motion(&self) -> bool {
+        self.rb.linear_velocity != Vec3::ZERO
+            || self.rb.angular_velocity != Vec3::ZERO
+            || self.rb.force != Vec3::ZERO
+            || self.rb.torque != Vec3::ZERO
+    }
+
+    /// Returns true if the body has been moved since the beginning of the step or substep.
+    pub fn has_moved(&self) -> bool {
+        self.rb.position != self.previous_position || self.rb.rotation != self.previous_rotation
+    }
+
+    pub fn collision_groups(&self) -> CollisionGroups {
+        self.rb.collision_groups
+    }
+
+    pub fn is_ccd_enabled(&self) -> bool {
+        self.rb.ccd
+    }
+
+    pub fn ccd_impact(&self) -> bool {
+        self.ccd_impact
+    }
+
+    pub fn set_ccd_impact(&mut self, ccd_impact: bool) {
+        self.ccd_impact = ccd_impact;
+    }
+
+    pub fn sleep_timer(&self) -> f32 {
+        self.rb.sleep_timer
+    }
+
+    pub fn set_sleep_timer(&mut self, sleep_timer: f32) {
+        self.rb.sleep_timer = sleep_timer;
+        self.is_dirty = true;
+    }
+
+    pub fn sleep(&mut self) {
+        self.rb.sleeping = true;
+        self.rb.linear_velocity = Vec3::ZERO;
+        self.rb.angular_velocity = Vec3::ZERO;
+        self.is_dirty = true;
+    }
+
+    pub fn wake_up(&mut self) {
+        self.rb.wake_up();
+        self.is_dirty = true;
+    }
+
+    /// Returns true if the body changed enough since it was last written to be written back to
+    /// the database. Bodies are written when their computed mass properties changed, static
+    /// bodies are otherwise never written and the other ones only when their sleeping state,
+    /// forces or velocities changed, or when they moved more than the world epsilons. The sleep
+    /// timer changes every step and is only written along with another change.
+    pub fn needs_update(&self, world: &PhysicsWorld) -> bool {
+        let stored = &self.stored;
+        if self.rb.mass_properties != stored.mass_properties {
+            return true;
+        }
+
+        if !self.is_dirty || self.rb.body_type == RigidBodyType::Static {
+            return false;
+        }
+
+        self.rb.sleeping != stored.sleeping
+            || self.rb.force != stored.force
+            || self.rb.torque != stored.torque
+            || (self.rb.position - stored.position).length() > world.position_write_epsilon
+            || self.rb.rotation.angle_between(stored.rotation) > world.rotation_write_epsilon
+            || (self.rb.linear_velocity - stored.linear_velocity).length()
+                > world.velocity_write_epsilon
+            || (self.rb.angular_velocity - stored.angular_velocity).length()
+                > world.velocity_write_epsilon
+    }
+
+    /// Returns true if the simulated state of the body differs from its row in the database.
+    pub fn has_unwritten_changes(&self) -> bool {
+        self.rb != self.stored
+    }
+
+    /// The row of the body in the database.
+    pub fn stored(&self) -> &RigidBody {
+        &self.stored
+    }
+
+    /// The simulated state of the body, written to the database by [`RigidBodyData::update`].
+    pub fn simulated(&self) -> &RigidBody {
+        &self.rb
+    }
+
+    /// Continues the simulation from a state that was not written back to the database by the
+    /// previous step.
+    pub(crate) fn restore(&mut self, simulated: &RigidBody) {
+        self.rb.position = simulated.position;
+        self.rb.rotation = simulated.rotation;
+        self.rb.linear_velocity = simulated.linear_velocity;
+        self.rb.angular_velocity = simulated.angular_velocity;
+        self.rb.sleep_timer = simulated.sleep_timer;
+        self.previous_position = simulated.position;
+        self.previous_rotation = simulated.rotation;
+        self.pre_solve_linear_velocity = simulated.linear_velocity;
+        self.pre_solve_angular_velocity = simulated.angular_velocity;
+    }
+
     pub fn inv_mass(&self) -> f32 {
         self.inv_mass
     }
@@ -155,8 +404,48 @@ impl RigidBodyData {
         self.rb.torque
     }
 
+    pub fn mass_properties(&self) -> ComputedMassProperties {
+        self.rb.mass_properties
+    }
+
+    pub fn center_of_mass(&self) -> Vec3 {
+        self.rb.mass_properties.center_of_mass
+    }
+
+    pub fn world_center_of_mass(&self) -> Vec3 {
+        self.world_point(self.center_of_mass())
+    }
+
+    pub fn previous_world_center_of_mass(&self) -> Vec3 {
+        self.previous_position + self.previous_rotation.rotate(self.center_of_mass())
+    }
+
+    /// Converts a point from the body's local space to world space.
+    pub fn world_point(&self, local_point: Vec3) -> Vec3 {
+        self.rb.position + self.rb.rotation.rotate(local_point)
+    }
+
+    /// Returns the vector from the center of mass to a point given in the body's local space,
+    /// in world space. This is the lever arm through which corrections rotate the body.
+    pub fn lever_arm(&self, local_point: Vec3) -> Vec3 {
+        self.rb.rotation.rotate(local_point - self.center_of_mass())
+    }
+
+    /// Returns the pose of the collider relative to the body origin.
+    pub fn local_collider_isometry(&self) -> Isometry3<f32> {
+        Isometry3::from_parts(
+            self.rb.collider_position.into(),
+            self.rb.collider_rotation.into(),
+        )
+    }
+
+    /// Returns the pose of the collider in world space, used by every shape query.
+    pub fn collider_isometry(&self) -> Isometry3<f32> {
+        Isometry3::from(self) * self.local_collider_isometry()
+    }
+
     pub fn inertia_tensor(&self) -> Mat3 {
-        self.inertia_tensor
+        self.rb.mass_properties.inertia_tensor
     }
 
     pub fn inv_inertia_tensor(&self) -> Mat3 {
@@ -181,6 +470,14 @@ impl RigidBodyData {
         self.is_dirty = true;
     }
 
+    /// Rotates the body around its center of mass instead of its origin, moving the origin
+    /// when the center of mass is offset.
+    pub fn set_rotation_around_center_of_mass(&mut self, rotation: Quat) {
+        let center_of_mass = self.world_center_of_mass();
+        self.set_rotation(rotation);
+        self.set_position(center_of_mass - self.rb.rotation.rotate(self.center_of_mass()));
+    }
+
     pub fn set_previous_position(&mut self, position: Vec3) {
         self.previous_position = position;
     }
@@ -191,6 +488,7 @@ impl RigidBodyData {
 
     pub fn set_linear_velocity(&mut self, velocity: Vec3) {
         self.rb.linear_velocity = velocity;
+        self.is_dirty = true;
     }
 
     pub fn set_angular_velocity(&mut self, velocity: Vec3) {
@@ -217,7 +515,7 @@ impl RigidBodyData {
     }
 
     pub fn update(&self, ctx: &ReducerContext) {
-        self.rb.update(ctx);
+        self.rb.update_from_simulation(ctx);
     }
 }
 
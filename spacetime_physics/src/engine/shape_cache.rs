@@ -0,0 +1,115 @@
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{math::Vec3, Collider, ColliderId, ComputedMassProperties, ShapeWrapper};
+
+type ShapeKey = (ColliderId, [u32; 3]);
+
+/// A shape built from a collider, along with what it was built from to detect when the
+/// collider, or one of the children of a compound, has been updated since.
+struct CachedShape {
+    collider: Collider,
+    children: Vec<Option<Collider>>,
+    shape: Rc<ShapeWrapper>,
+    /// The mass properties for a density of 1.0, not computed for static bodies.
+    unit_mass_properties: Option<ComputedMassProperties>,
+}
+
+impl CachedShape {
+    fn new(collider: &Collider, colliders: &HashMap<ColliderId, Collider>, scale: Vec3) -> Self {
+        Self {
+            collider: collider.clone(),
+            children: Self::children(collider, colliders)
+                .map(|child| child.cloned())
+                .collect(),
+            shape: Rc::new(ShapeWrapper::new(collider, colliders, scale)),
+            unit_mass_properties: None,
+        }
+    }
+
+    fn is_up_to_date(
+        &self,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+    ) -> bool {
+        self.collider == *collider
+            && self.children.len() == collider.children.len()
+            && self
+                .children
+                .iter()
+                .zip(Self::children(collider, colliders))
+                .all(|(cached, current)| cached.as_ref() == current)
+    }
+
+    fn children<'a>(
+        collider: &'a Collider,
+        colliders: &'a HashMap<ColliderId, Collider>,
+    ) -> impl Iterator<Item = Option<&'a Collider>> + 'a {
+        collider
+            .children
+            .iter()
+            .map(|child| colliders.get(&child.collider_id))
+    }
+}
+
+/// The shapes of the colliders of a world keyed by collider and scale, kept in the
+/// [`WorldCache`](super::world_cache::WorldCache) so that shapes that are expensive to build,
+/// e.g. triangle meshes and their BVH, are only built again when their collider changes.
+#[derive(Default)]
+pub(crate) struct ShapeCache {
+    shapes: HashMap<ShapeKey, CachedShape>,
+    used: HashSet<ShapeKey>,
+}
+
+impl ShapeCache {
+    /// Returns the shape of a collider scaled along its local axes, see [`ShapeWrapper::new`].
+    pub fn shape(
+        &mut self,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+        scale: Vec3,
+    ) -> Rc<ShapeWrapper> {
+        self.entry(collider, colliders, scale).shape.clone()
+    }
+
+    /// Returns the mass properties of the shape of a collider for a density of 1.0, see
+    /// [`ShapeWrapper::mass_properties`].
+    pub fn unit_mass_properties(
+        &mut self,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+        scale: Vec3,
+    ) -> ComputedMassProperties {
+        let entry = self.entry(collider, colliders, scale);
+        *entry
+            .unit_mass_properties
+            .get_or_insert_with(|| entry.shape.mass_properties(1.0))
+    }
+
+    /// Drops the shapes that have not been used since the last call.
+    pub fn retain_used(&mut self) {
+        let used = std::mem::take(&mut self.used);
+        self.shapes.retain(|key, _| used.contains(key));
+    }
+
+    fn entry(
+        &mut self,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+        scale: Vec3,
+    ) -> &mut CachedShape {
+        let key = (collider.id, [scale.x, scale.y, scale.z].map(f32::to_bits));
+        self.used.insert(key);
+
+        let entry = self
+            .shapes
+            .entry(key)
+            .or_insert_with(|| CachedShape::new(collider, colliders, scale));
+        if !entry.is_up_to_date(collider, colliders) {
+            *entry = CachedShape::new(collider, colliders, scale);
+        }
+        entry
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use log::warn;
+use parry3d::{
+    bounding_volume::Aabb,
+    na::{Isometry3, Vector3},
+};
+use spacetimedb::ReducerContext;
+
+use crate::{math::Vec3, Collider, ColliderId, ShapeCast, ShapeWrapper};
+
+/// A [`ShapeCast`] row along with the shape of its collider.
+#[derive(Debug)]
+pub struct ShapeCastData {
+    pub shape_cast: ShapeCast,
+    pub shape: ShapeWrapper,
+    pub isometry: Isometry3<f32>,
+    /// The normalized sweep direction, the time of impact is then the travelled distance.
+    pub velocity: Vector3<f32>,
+}
+
+impl ShapeCastData {
+    pub fn new(
+        shape_cast: ShapeCast,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+    ) -> Self {
+        Self {
+            shape: ShapeWrapper::new(collider, colliders, Vec3::ONE),
+            isometry: Isometry3::from_parts(shape_cast.origin.into(), shape_cast.rotation.into()),
+            velocity: shape_cast.direction.normalize_or_zero().into(),
+            shape_cast,
+        }
+    }
+
+    pub fn collect(
+        ctx: &ReducerContext,
+        world_id: u64,
+        colliders: &HashMap<ColliderId, Collider>,
+    ) -> Vec<Self> {
+        ShapeCast::all(ctx, world_id)
+            .filter_map(|shape_cast| {
+                let Some(collider) = colliders.get(&shape_cast.collider_id) else {
+                    warn!(
+                        "ShapeCast#{} references the missing collider {}, skipping it",
+                        shape_cast.id, shape_cast.collider_id
+                    );
+                    return None;
+                };
+                if !shape_cast.max_distance.is_finite() || shape_cast.max_distance < 0.0 {
+                    warn!(
+                        "ShapeCast#{} has an invalid max distance {}, skipping it",
+                        shape_cast.id, shape_cast.max_distance
+                    );
+                    return None;
+                }
+                Some(ShapeCastData::new(shape_cast, collider, colliders))
+            })
+            .collect()
+    }
+
+    /// Returns the bounding box covering the shape along its whole sweep.
+    pub fn swept_aabb(&self) -> Aabb {
+        self.shape.swept_aabb(
+            &self.isometry,
+            &(self.velocity * self.shape_cast.max_distance),
+        )
+    }
+
+    pub fn update(&self, ctx: &ReducerContext) {
+        self.shape_cast.clone().update(ctx);
+    }
+}
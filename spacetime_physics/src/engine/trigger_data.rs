@@ -1,17 +1,27 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use parry3d::na::Isometry3;
 use spacetimedb::ReducerContext;
 
-use crate::{Collider, ColliderId, RigidBodyId, ShapeWrapper, Trigger, TriggerId};
+use crate::{
+    math::Vec3, Collider, ColliderId, CollisionGroups, RigidBodyId, ShapeWrapper, Trigger,
+    TriggerId,
+};
+
+use super::shape_cache::ShapeCache;
 
 #[derive(Debug)]
 pub struct TriggerData {
-    pub shape: ShapeWrapper,
+    pub shape: Rc<ShapeWrapper>,
     pub trigger_id: TriggerId,
     pub collider_id: ColliderId,
     pub world_id: u64,
+    pub scale: Vec3,
     pub isometry: Isometry3<f32>,
+    pub collision_groups: CollisionGroups,
     pub current_entities_inside: HashSet<RigidBodyId>,
     pub new_entities_inside: HashSet<RigidBodyId>,
     pub added_entities: HashSet<RigidBodyId>,
@@ -19,13 +29,20 @@ pub struct TriggerData {
 }
 
 impl TriggerData {
-    pub fn new(trigger: &Trigger, collider: &Collider) -> Self {
+    pub fn new(
+        trigger: &Trigger,
+        collider: &Collider,
+        colliders: &HashMap<ColliderId, Collider>,
+        shapes: &mut ShapeCache,
+    ) -> Self {
         Self {
             collider_id: collider.id,
             trigger_id: trigger.id,
             world_id: trigger.world_id,
-            shape: ShapeWrapper::from(collider),
+            shape: shapes.shape(collider, colliders, trigger.scale),
+            scale: trigger.scale,
             isometry: Isometry3::from_parts(trigger.position.into(), trigger.rotation.into()),
+            collision_groups: trigger.collision_groups,
             current_entities_inside: trigger.entities_inside.iter().copied().collect(),
             added_entities: HashSet::new(),
             removed_entities: HashSet::new(),
@@ -37,11 +54,12 @@ impl TriggerData {
         ctx: &ReducerContext,
         world_id: u64,
         colliders: &HashMap<ColliderId, Collider>,
+        shapes: &mut ShapeCache,
     ) -> Vec<Self> {
         Trigger::all(ctx, world_id)
             .map(|trigger| {
                 let collider = colliders.get(&trigger.collider_id).unwrap();
-                TriggerData::new(&trigger, collider)
+                TriggerData::new(&trigger, collider, colliders, shapes)
             })
             .collect()
     }
@@ -53,6 +71,8 @@ impl TriggerData {
             position: self.isometry.translation.vector.into(),
             rotation: self.isometry.rotation.into(),
             collider_id: self.collider_id,
+            scale: self.scale,
+            collision_groups: self.collision_groups,
             entities_inside: self.current_entities_inside.iter().cloned().collect(),
             added_entities: self.added_entities.iter().cloned().collect(),
             removed_entities: self.removed_entities.iter().cloned().collect(),
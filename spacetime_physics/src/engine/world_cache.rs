@@ -0,0 +1,194 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+use parry3d::query::ContactManifoldsWorkspace;
+
+use crate::{ContactManifold, PhysicsWorld, PhysicsWorldId, RigidBody, RigidBodyId};
+
+use super::{
+    broad_phase::BroadPhase, collision_detection::Collidable, constraints::PenetrationConstraint,
+    rigid_body_data::RigidBodyData, shape_cache::ShapeCache,
+};
+
+thread_local! {
+    static WORLD_CACHES: RefCell<HashMap<PhysicsWorldId, WorldCache>> = RefCell::new(HashMap::new());
+}
+
+/// The contact manifolds of a pair of bodies.
+#[derive(Default)]
+pub(crate) struct ContactPair {
+    pub manifolds: Vec<ContactManifold>,
+    pub workspace: Option<ContactManifoldsWorkspace>,
+}
+
+/// A body whose state at the end of a step was not written back to the database because it
+/// changed less than the world epsilons since it was last written.
+pub(crate) struct UnwrittenBody {
+    /// The row of the body in the database.
+    pub stored: RigidBody,
+    /// The state of the body at the end of the step.
+    pub simulated: RigidBody,
+}
+
+/// Simulation state kept in memory between two steps of a world. None of it is stored in the
+/// database, losing it (e.g. when the module is updated) only makes the next step start cold.
+///
+/// Memory is not rolled back when a reducer fails, so the cache is tagged with the step count
+/// of the world it was built for and discarded when it does not match the database anymore.
+#[derive(Default)]
+pub(crate) struct WorldCache {
+    /// The step count of the world once the step that built the cache has been committed.
+    step_count: u64,
+    /// Whether a trigger or collider has been written outside of a step since the cache was
+    /// stored, the broad phase may then miss it until the next step.
+    is_dirty: bool,
+    /// The bodies written outside of a step since the cache was stored. The broad phase may miss
+    /// them until the next step, the queries load them on top of it.
+    dirty_bodies: HashSet<RigidBodyId>,
+    /// Contact manifolds keyed by the ids of the two bodies, the smallest id first.
+    pub contacts: HashMap<(RigidBodyId, RigidBodyId), ContactPair>,
+    /// The bounding volume hierarchies of the bodies and triggers, refitted every step.
+    pub broad_phase: BroadPhase,
+    /// The shapes of the colliders used by the bodies and triggers of the last step.
+    pub shapes: ShapeCache,
+    /// The bodies that were not written back by the last step, keyed by their id.
+    pub unwritten_bodies: HashMap<RigidBodyId, UnwrittenBody>,
+}
+
+impl WorldCache {
+    /// Takes the cache of a world out of the module state, an empty one is returned if the
+    /// world has never been stepped or if the cache does not match its step count.
+    pub fn take(world: &PhysicsWorld) -> Self {
+        WORLD_CACHES.with(|caches| {
+            caches
+                .borrow_mut()
+                .remove(&world.id)
+                .filter(|cache| cache.step_count == world.step_count)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Puts the cache back into the module state so that the next step can use it, `world`
+    /// being the world row written at the end of the step.
+    pub fn store(mut self, world: &PhysicsWorld) {
+        self.step_count = world.step_count;
+        self.is_dirty = false;
+        self.dirty_bodies.clear();
+        WORLD_CACHES.with(|caches| caches.borrow_mut().insert(world.id, self));
+    }
+
+    /// Calls `f` with the cache of a world, `None` is returned if the world has not been
+    /// stepped since the module started, if the cache does not match its step count or if it
+    /// has been invalidated since.
+    pub fn with<R>(world: &PhysicsWorld, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        WORLD_CACHES.with(|caches| {
+            caches
+                .borrow_mut()
+                .get_mut(&world.id)
+                .filter(|cache| cache.step_count == world.step_count && !cache.is_dirty)
+                .map(f)
+        })
+    }
+
+    /// Marks the cache of a world as out of date, called when a trigger is inserted, updated or
+    /// deleted outside of a step, or when a collider is updated or deleted. The next step still reuses it as it synchronizes
+    /// the broad phase with the database anyway.
+    pub fn invalidate(world_id: PhysicsWorldId) {
+        WORLD_CACHES.with(|caches| {
+            if let Some(cache) = caches.borrow_mut().get_mut(&world_id) {
+                cache.is_dirty = true;
+            }
+        });
+    }
+
+    /// Marks a body as written since the cache was stored, called when it is inserted, updated
+    /// or deleted outside of a step. The rest of the broad phase stays usable by the queries.
+    pub fn invalidate_body(world_id: PhysicsWorldId, id: RigidBodyId) {
+        WORLD_CACHES.with(|caches| {
+            if let Some(cache) = caches.borrow_mut().get_mut(&world_id) {
+                cache.dirty_bodies.insert(id);
+            }
+        });
+    }
+
+    /// The bodies written outside of a step since the cache was stored.
+    pub fn dirty_bodies(&self) -> &HashSet<RigidBodyId> {
+        &self.dirty_bodies
+    }
+
+    /// Saves the multipliers accumulated by the contact constraints into their manifold points.
+    pub fn store_contact_lagrange(&mut self, constraints: &[PenetrationConstraint]) {
+        for constraint in constraints {
+            let Some(pair) = self.contacts.get_mut(&(constraint.a, constraint.b)) else {
+                continue;
+            };
+
+            if let Some(point) = pair
+                .manifolds
+                .get_mut(constraint.manifold_index)
+                .and_then(|manifold| manifold.points.get_mut(constraint.point_index))
+            {
+                point.data.normal_lagrange = constraint.normal_lagrange;
+            }
+        }
+    }
+
+    /// Restores the state of the bodies that were not written back by the last step, unless their
+    /// row has been updated since. Otherwise a body moving less than the world epsilons every
+    /// step would start each step from its last written pose and never move.
+    pub fn restore_unwritten_bodies(&mut self, bodies: &mut [RigidBodyData]) {
+        for body in bodies.iter_mut() {
+            if let Some(unwritten) = self.unwritten_bodies.get(&body.id) {
+                if *body.stored() == unwritten.stored {
+                    body.restore(&unwritten.simulated);
+                }
+            }
+        }
+        self.unwritten_bodies.clear();
+    }
+
+    /// Keeps the state of a body that is not written back to the database for the next step.
+    pub fn store_unwritten_body(&mut self, body: &RigidBodyData) {
+        self.unwritten_bodies.insert(
+            body.id,
+            UnwrittenBody {
+                stored: *body.stored(),
+                simulated: *body.simulated(),
+            },
+        );
+    }
+
+    /// Drops the manifolds of the pairs that are no longer overlapping in the broad phase.
+    pub fn retain_contacts(&mut self, pairs: &HashSet<(Collidable, Collidable)>) {
+        let pairs: HashSet<_> = pairs
+            .iter()
+            .filter(|(a, b)| !a.is_trigger && !b.is_trigger)
+            .map(|(a, b)| (a.id, b.id))
+            .collect();
+        self.contacts.retain(|key, _| pairs.contains(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn written_bodies_keep_the_cache_usable() {
+        let world = PhysicsWorld::builder().id(7).build();
+        WorldCache::default().store(&world);
+
+        WorldCache::invalidate_body(world.id, 3);
+        let dirty_bodies = WorldCache::with(&world, |cache| cache.dirty_bodies().clone());
+        assert_eq!(dirty_bodies, Some(HashSet::from([3])));
+
+        WorldCache::invalidate(world.id);
+        assert!(WorldCache::with(&world, |_| ()).is_none());
+
+        WorldCache::take(&world).store(&world);
+        let dirty_bodies = WorldCache::with(&world, |cache| cache.dirty_bodies().clone());
+        assert_eq!(dirty_bodies, Some(HashSet::new()));
+    }
+}
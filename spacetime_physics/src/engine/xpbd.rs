@@ -7,7 +7,9 @@ use crate::{
 };
 
 use super::{
-    constraints::{Constraint, PenetrationConstraint, PositionConstraint},
+    constraints::{
+        Constraint, DistanceConstraint, JointConstraint, PenetrationConstraint, PositionConstraint,
+    },
     RigidBodyData,
 };
 
@@ -18,7 +20,7 @@ pub(crate) fn integrate_bodies(
 ) {
     let sw = world.stopwatch("integrate_bodies");
     for body in bodies {
-        if !body.is_dynamic() {
+        if !body.is_active() {
             continue;
         }
 
@@ -33,6 +35,14 @@ pub(crate) fn integrate_bodies(
             body.linear_velocity() + total_force * body.effective_inverse_mass() * delta_time,
         );
 
+        // v ← v * e^(-h * d), applying the damping over n substeps is the same as applying it
+        // once over the whole step, making it independent of the time step
+        body.set_linear_velocity(
+            body.linear_velocity()
+                * damping_factor(body.linear_damping(), delta_time)
+                * body.locked_axes().translation_mask(),
+        );
+
         // x ← x + h * v
         body.set_position(body.position() + body.linear_velocity() * delta_time);
 
@@ -55,9 +65,16 @@ pub(crate) fn integrate_bodies(
         // ω ← ω + h * α
         body.set_angular_velocity(body.angular_velocity() + delta_time * angular_acceleration);
 
+        // ω ← ω * e^(-h * d)
+        body.set_angular_velocity(
+            body.angular_velocity()
+                * damping_factor(body.angular_damping(), delta_time)
+                * body.locked_axes().rotation_mask(),
+        );
+
         // q ← q + 0.5 * h * q × ω
         let dq = 0.5 * delta_time * body.rotation() * Quat::from_xyz(body.angular_velocity(), 0.0);
-        body.set_rotation(body.rotation() + dq);
+        body.set_rotation_around_center_of_mass(body.rotation() + dq);
 
         body.set_torque(Vec3::ZERO);
         body.set_force(Vec3::ZERO);
@@ -72,13 +89,33 @@ pub(crate) fn integrate_bodies(
     sw.end();
 }
 
+pub(crate) fn warm_start_constraints(
+    world: &PhysicsWorld,
+    contact_constraints: &mut [PenetrationConstraint],
+    bodies: &mut [RigidBodyData],
+) {
+    let sw = world.stopwatch("warm_start_constraints");
+    contact_constraints
+        .iter_mut()
+        .for_each(|constraint| constraint.warm_start(bodies));
+    sw.end();
+}
+
 pub(crate) fn solve_constraints(
     world: &PhysicsWorld,
     contact_constraints: &mut [PenetrationConstraint],
+    joint_constraints: &mut [JointConstraint],
+    distance_constraints: &mut [DistanceConstraint],
     bodies: &mut [RigidBodyData],
     delta_time: f32,
 ) {
     let sw = world.stopwatch("solve_constraints");
+    joint_constraints
+        .iter_mut()
+        .for_each(|constraint| constraint.solve(world, bodies, delta_time));
+    distance_constraints
+        .iter_mut()
+        .for_each(|constraint| constraint.solve(world, bodies, delta_time));
     contact_constraints
         .iter_mut()
         .for_each(|constraint| constraint.solve(world, bodies, delta_time));
@@ -94,8 +131,21 @@ pub(crate) fn recompute_velocities(world: &PhysicsWorld, bodies: &mut [RigidBody
             continue;
         }
 
+        if body.is_sleeping() {
+            continue;
+        }
+
         body.set_pre_solve_linear_velocity(body.linear_velocity());
-        body.set_linear_velocity((body.position() - body.previous_position()) / dt);
+
+        // The motion of bodies stopped by continuous collision detection does not reflect their
+        // velocity, it is kept so that the contact generated at the next substep can respond to it
+        if body.ccd_impact() {
+            body.set_ccd_impact(false);
+        } else {
+            body.set_linear_velocity(
+                (body.world_center_of_mass() - body.previous_world_center_of_mass()) / dt,
+            );
+        }
 
         body.set_pre_solve_angular_velocity(body.angular_velocity());
         body.set_angular_velocity(
@@ -124,7 +174,12 @@ pub(crate) fn solve_velocities(
 ) {
     let sw = world.stopwatch("solve_velocities");
     for constraint in penetration_constraints {
+        if !constraint.is_touching() {
+            continue;
+        }
+
         let (body1, body2) = get_bodies_mut(constraint.a, constraint.b, bodies);
+        let (r1, r2) = constraint.lever_arms(body1, body2);
         let normal = constraint.normal;
         let gravity = world.gravity;
 
@@ -132,27 +187,21 @@ pub(crate) fn solve_velocities(
         let pre_solve_contact_vel1 = compute_contact_vel(
             body1.pre_solve_linear_velocity(),
             body1.pre_solve_angular_velocity(),
-            constraint.world_a,
+            r1,
         );
         let pre_solve_contact_vel2 = compute_contact_vel(
             body2.pre_solve_linear_velocity(),
             body2.pre_solve_angular_velocity(),
-            constraint.world_b,
+            r2,
         );
         let pre_solve_relative_vel = pre_solve_contact_vel1 - pre_solve_contact_vel2;
         let pre_solve_normal_vel = normal.dot(pre_solve_relative_vel);
 
         // Compute relative normal and tangential velocities at the contact point (equation 29)
-        let contact_vel1 = compute_contact_vel(
-            body1.linear_velocity(),
-            body1.angular_velocity(),
-            constraint.world_a,
-        );
-        let contact_vel2 = compute_contact_vel(
-            body2.linear_velocity(),
-            body2.angular_velocity(),
-            constraint.world_b,
-        );
+        let contact_vel1 =
+            compute_contact_vel(body1.linear_velocity(), body1.angular_velocity(), r1);
+        let contact_vel2 =
+            compute_contact_vel(body2.linear_velocity(), body2.angular_velocity(), r2);
         let relative_vel = contact_vel1 - contact_vel2;
         let normal_vel = normal.dot(relative_vel);
         let tangent_vel = relative_vel - normal * normal_vel;
@@ -194,25 +243,21 @@ pub(crate) fn solve_velocities(
         let delta_v_dir = delta_v / delta_v_length;
 
         // Compute generalized inverse masses
-        let w1 =
-            constraint.compute_generalized_inverse_mass(body1, &constraint.world_a, &delta_v_dir);
-        let w2 =
-            constraint.compute_generalized_inverse_mass(body2, &constraint.world_b, &delta_v_dir);
+        let w1 = constraint.compute_generalized_inverse_mass(body1, &r1, &delta_v_dir);
+        let w2 = constraint.compute_generalized_inverse_mass(body2, &r2, &delta_v_dir);
 
         // Compute velocity impulse and apply velocity updates (equation 33)
         let p = delta_v / (w1 + w2);
         if body1.is_dynamic() {
             body1.set_linear_velocity(body1.linear_velocity() + p * inv_mass1);
             body1.set_angular_velocity(
-                body1.angular_velocity()
-                    + compute_delta_ang_vel(inv_inertia1, constraint.world_a, p),
+                body1.angular_velocity() + compute_delta_ang_vel(inv_inertia1, r1, p),
             );
         }
         if body2.is_dynamic() {
             body2.set_linear_velocity(body2.linear_velocity() - p * inv_mass2);
             body2.set_angular_velocity(
-                body2.angular_velocity()
-                    - compute_delta_ang_vel(inv_inertia2, constraint.world_b, p),
+                body2.angular_velocity() - compute_delta_ang_vel(inv_inertia2, r2, p),
             );
         }
 
@@ -226,6 +271,10 @@ pub(crate) fn solve_velocities(
     sw.end();
 }
 
+fn damping_factor(damping: f32, delta_time: f32) -> f32 {
+    (-damping * delta_time).exp()
+}
+
 fn compute_contact_vel(lin_vel: Vec3, ang_vel: Vec3, r: Vec3) -> Vec3 {
     lin_vel + ang_vel.cross(r)
 }
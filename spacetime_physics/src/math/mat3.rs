@@ -175,3 +175,19 @@ impl Mul<Mat3> for f32 {
         }
     }
 }
+
+impl From<parry3d::na::Matrix3<f32>> for Mat3 {
+    fn from(value: parry3d::na::Matrix3<f32>) -> Self {
+        Mat3::new(
+            value[(0, 0)],
+            value[(0, 1)],
+            value[(0, 2)],
+            value[(1, 0)],
+            value[(1, 1)],
+            value[(1, 2)],
+            value[(2, 0)],
+            value[(2, 1)],
+            value[(2, 2)],
+        )
+    }
+}
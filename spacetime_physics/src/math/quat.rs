@@ -119,6 +119,15 @@ impl Quat {
         Vec3::new(self.x, self.y, self.z)
     }
 
+    pub fn dot(&self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Returns the angle in radians of the shortest rotation between two unit quaternions.
+    pub fn angle_between(&self, other: Self) -> f32 {
+        2.0 * self.dot(other).abs().min(1.0).acos()
+    }
+
     pub fn rotate(&self, vec: Vec3) -> Vec3 {
         let uv = Vec3::new(
             self.y * vec.z - self.z * vec.y,
@@ -144,6 +144,14 @@ impl Vec3 {
             z: self.z.min(other.z),
         }
     }
+
+    pub fn max(self, other: Vec3) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
 }
 
 impl Hash for Vec3 {
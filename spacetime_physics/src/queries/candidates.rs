@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use parry3d::{bounding_volume::Aabb, query::Ray};
+use spacetimedb::ReducerContext;
+
+use crate::{
+    engine::{BroadPhase, ShapeCache, TriggerData, WorldCache},
+    Collider, ColliderId, PhysicsWorld, PhysicsWorldId, RigidBody, RigidBodyData, RigidBodyId,
+    RigidBodyProperties, Trigger,
+};
+
+// The instant queries use the broad phase kept in memory by `step_world` to only load the
+// bodies and triggers whose bounding box, as of the end of the last step, can match, along
+// with their colliders, and reuses the shapes it built. The bodies written since the last step
+// are always loaded as they may have moved. Every body is loaded instead when the world has not
+// been stepped since the module started, or when a trigger or collider has been written since.
+
+/// Loads the bodies whose bounding box intersects `aabb`.
+pub(crate) fn bodies_in_aabb(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    aabb: &Aabb,
+) -> Vec<RigidBodyData> {
+    bodies_from_broad_phase(ctx, world_id, |broad_phase| {
+        broad_phase.rigid_bodies_in_aabb(aabb)
+    })
+}
+
+/// Loads the bodies whose bounding box is hit by the ray.
+pub(crate) fn bodies_along_ray(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    ray: &Ray,
+    max_distance: f32,
+) -> Vec<RigidBodyData> {
+    bodies_from_broad_phase(ctx, world_id, |broad_phase| {
+        broad_phase.rigid_bodies_along_ray(ray, max_distance)
+    })
+}
+
+/// Loads a single body, `None` if it does not exist in this world.
+pub(crate) fn find_body(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    id: RigidBodyId,
+) -> Option<RigidBodyData> {
+    let rb = RigidBody::find(ctx, id).filter(|rb| rb.world_id == world_id)?;
+    with_cache(ctx, world_id, |cache| {
+        load_bodies(ctx, vec![rb], &mut cache.shapes)
+    })
+    .unwrap_or_else(|| load_bodies(ctx, vec![rb], &mut ShapeCache::default()))
+    .pop()
+}
+
+/// Loads the triggers whose bounding box intersects `aabb`.
+pub(crate) fn triggers_in_aabb(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    aabb: &Aabb,
+) -> Vec<TriggerData> {
+    with_cache(ctx, world_id, |cache| {
+        let triggers: Vec<_> = cache
+            .broad_phase
+            .triggers_in_aabb(aabb)
+            .into_iter()
+            .filter_map(|id| Trigger::find(ctx, id))
+            .filter(|trigger| trigger.world_id == world_id)
+            .collect();
+        let colliders = load_colliders(ctx, triggers.iter().map(|trigger| trigger.collider_id));
+        triggers
+            .iter()
+            .filter_map(|trigger| {
+                let collider = colliders.get(&trigger.collider_id)?;
+                Some(TriggerData::new(
+                    trigger,
+                    collider,
+                    &colliders,
+                    &mut cache.shapes,
+                ))
+            })
+            .collect()
+    })
+    .unwrap_or_else(|| {
+        TriggerData::collect(
+            ctx,
+            world_id,
+            &Collider::all(ctx, world_id),
+            &mut ShapeCache::default(),
+        )
+    })
+}
+
+/// Loads the bodies found by `query` in the broad phase of the last step along with the bodies
+/// written since, or every body of the world when the broad phase may be out of date.
+fn bodies_from_broad_phase(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    query: impl FnOnce(&BroadPhase) -> Vec<RigidBodyId>,
+) -> Vec<RigidBodyData> {
+    with_cache(ctx, world_id, |cache| {
+        let mut ids = query(&cache.broad_phase);
+        ids.extend(cache.dirty_bodies());
+        ids.sort_unstable();
+        ids.dedup();
+        let rigid_bodies = ids
+            .into_iter()
+            .filter_map(|id| RigidBody::find(ctx, id))
+            .filter(|rb| rb.world_id == world_id)
+            .collect();
+        load_bodies(ctx, rigid_bodies, &mut cache.shapes)
+    })
+    .unwrap_or_else(|| RigidBodyData::collect(ctx, world_id, &Collider::all(ctx, world_id)))
+}
+
+/// Calls `f` with the cache of the last step of a world, `None` if there is none or if it may
+/// be out of date.
+fn with_cache<R>(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    f: impl FnOnce(&mut WorldCache) -> R,
+) -> Option<R> {
+    let world = PhysicsWorld::find(ctx, world_id)?;
+    WorldCache::with(&world, f)
+}
+
+/// Loads the colliders and properties of the given bodies, each row only once as bodies often
+/// share them, and builds their shapes from `shapes`.
+fn load_bodies(
+    ctx: &ReducerContext,
+    rigid_bodies: Vec<RigidBody>,
+    shapes: &mut ShapeCache,
+) -> Vec<RigidBodyData> {
+    let colliders = load_colliders(ctx, rigid_bodies.iter().map(|rb| rb.collider_id));
+    let mut properties = HashMap::new();
+    rigid_bodies
+        .into_iter()
+        .filter_map(|rb| {
+            let rb_properties = properties
+                .entry(rb.properties_id)
+                .or_insert_with(|| RigidBodyProperties::find(ctx, rb.properties_id))
+                .as_ref()?;
+            let collider = colliders.get(&rb.collider_id)?;
+            Some(RigidBodyData::with_shapes(
+                rb,
+                rb_properties,
+                collider,
+                &colliders,
+                shapes,
+            ))
+        })
+        .collect()
+}
+
+/// Loads the given colliders along with the children of the compound ones.
+fn load_colliders(
+    ctx: &ReducerContext,
+    ids: impl Iterator<Item = ColliderId>,
+) -> HashMap<ColliderId, Collider> {
+    let mut colliders = HashMap::new();
+    let mut pending: Vec<_> = ids.collect();
+    while let Some(id) = pending.pop() {
+        if colliders.contains_key(&id) {
+            continue;
+        }
+        if let Some(collider) = Collider::find(ctx, id) {
+            pending.extend(collider.children.iter().map(|child| child.collider_id));
+            colliders.insert(id, collider);
+        }
+    }
+    colliders
+}
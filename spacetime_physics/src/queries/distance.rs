@@ -0,0 +1,164 @@
+use std::fmt::Display;
+
+use parry3d::{bounding_volume::Aabb, math::Point};
+use spacetimedb::ReducerContext;
+
+use crate::{math::Vec3, CollisionGroups, PhysicsWorldId, RigidBodyData, RigidBodyId};
+
+use super::candidates::{bodies_in_aabb, find_body};
+
+/// The projection of a point on the shape of a rigid body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointProjection {
+    /// The closest point on the body, in world coordinates.
+    pub point: Vec3,
+
+    /// The distance between the projected point and the body, 0.0 if it is inside.
+    pub distance: f32,
+
+    /// Whether the projected point is inside the body.
+    pub is_inside: bool,
+
+    /// The ID of the rigid body the point was projected on.
+    pub rigid_body_id: RigidBodyId,
+}
+
+impl Display for PointProjection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PointProjection {{ point: {}, distance: {}, is_inside: {}, rigid_body_id: {} }}",
+            self.point, self.distance, self.is_inside, self.rigid_body_id
+        )
+    }
+}
+
+/// Projects a point on a body, `None` if the body does not exist in this world. When `solid`
+/// is true points inside the body are projected on themselves, otherwise on its boundary.
+pub fn project_point(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    rigid_body_id: RigidBodyId,
+    point: Vec3,
+    solid: bool,
+) -> Option<PointProjection> {
+    let body = find_body(ctx, world_id, rigid_body_id)?;
+    Some(project_point_on_body(&body, point, solid))
+}
+
+/// Returns the projection of a point on the closest body within `max_distance`, e.g. to find
+/// the nearest cover for an AI.
+pub fn closest_body(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    point: Vec3,
+    max_distance: f32,
+    collision_groups: CollisionGroups,
+) -> Option<PointProjection> {
+    let aabb = Aabb::from_half_extents(point.into(), Vec3::splat(max_distance).into());
+    let bodies = bodies_in_aabb(ctx, world_id, &aabb).into_iter();
+    closest_body_with_rigid_bodies(bodies, point, max_distance, collision_groups)
+}
+
+pub fn closest_body_with_rigid_bodies(
+    entities: impl Iterator<Item = RigidBodyData>,
+    point: Vec3,
+    max_distance: f32,
+    collision_groups: CollisionGroups,
+) -> Option<PointProjection> {
+    entities
+        .filter(|body| body.collision_groups().interacts_with(&collision_groups))
+        .map(|body| project_point_on_body(&body, point, true))
+        .filter(|projection| projection.distance <= max_distance)
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+/// Returns the distance between the shapes of two bodies, 0.0 if they overlap, or `None` if
+/// one of them does not exist in this world or parry does not support their pair of shapes.
+pub fn distance(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    body_a: RigidBodyId,
+    body_b: RigidBodyId,
+) -> Option<f32> {
+    let body_a = find_body(ctx, world_id, body_a)?;
+    let body_b = find_body(ctx, world_id, body_b)?;
+    body_a.shape().distance(
+        &body_a.collider_isometry(),
+        &body_b.collider_isometry(),
+        body_b.shape(),
+    )
+}
+
+pub(crate) fn project_point_on_body(
+    body: &RigidBodyData,
+    point: Vec3,
+    solid: bool,
+) -> PointProjection {
+    let projection =
+        body.shape()
+            .project_point(&body.collider_isometry(), &Point::from(point), solid);
+    let projected: Vec3 = projection.point.into();
+
+    PointProjection {
+        point: projected,
+        distance: if projection.is_inside {
+            0.0
+        } else {
+            (projected - point).length()
+        },
+        is_inside: projection.is_inside,
+        rigid_body_id: body.id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sphere_body;
+
+    fn bodies() -> impl Iterator<Item = RigidBodyData> {
+        [Vec3::new(5.0, 0.0, 0.0), Vec3::new(0.0, -3.0, 0.0)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, position)| sphere_body(i as u64 + 1, position))
+    }
+
+    #[test]
+    fn closest_body_returns_the_nearest_projection() {
+        let projection =
+            closest_body_with_rigid_bodies(bodies(), Vec3::ZERO, 10.0, CollisionGroups::ALL)
+                .unwrap();
+
+        assert_eq!(projection.rigid_body_id, 2);
+        assert!((projection.distance - 2.5).abs() < 1e-4);
+        assert!((projection.point - Vec3::new(0.0, -2.5, 0.0)).length() < 1e-4);
+        assert!(!projection.is_inside);
+    }
+
+    #[test]
+    fn closest_body_ignores_bodies_beyond_max_distance_or_filtered() {
+        assert!(
+            closest_body_with_rigid_bodies(bodies(), Vec3::ZERO, 2.0, CollisionGroups::ALL)
+                .is_none()
+        );
+        assert!(
+            closest_body_with_rigid_bodies(bodies(), Vec3::ZERO, 10.0, CollisionGroups::NONE)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn project_point_on_body_reports_inside_points() {
+        let body = sphere_body(1, Vec3::ZERO);
+        let point = Vec3::new(0.2, 0.0, 0.0);
+
+        let solid = project_point_on_body(&body, point, true);
+        assert!(solid.is_inside);
+        assert_eq!((solid.point, solid.distance), (point, 0.0));
+
+        let hollow = project_point_on_body(&body, point, false);
+        assert!(hollow.is_inside);
+        assert!((hollow.point - Vec3::new(0.5, 0.0, 0.0)).length() < 1e-4);
+    }
+}
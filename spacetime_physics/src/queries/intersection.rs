@@ -0,0 +1,124 @@
+use parry3d::{
+    bounding_volume::{Aabb, BoundingVolume},
+    math::Point,
+    na::Isometry3,
+};
+use spacetimedb::ReducerContext;
+
+use crate::{
+    math::{Quat, Vec3},
+    CollisionGroups, PhysicsWorldId, RigidBodyId, ShapeWrapper, TriggerId,
+};
+
+use super::candidates::{bodies_in_aabb, triggers_in_aabb};
+
+/// The rigid bodies and triggers matching an overlap query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Intersections {
+    pub rigid_bodies: Vec<RigidBodyId>,
+    /// Only filled when the query includes triggers.
+    pub triggers: Vec<TriggerId>,
+}
+
+impl Intersections {
+    pub fn is_empty(&self) -> bool {
+        self.rigid_bodies.is_empty() && self.triggers.is_empty()
+    }
+}
+
+/// Returns the bodies, and optionally the triggers, containing a point, e.g. to check if a
+/// spawn point is free.
+pub fn intersect_point(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    point: Vec3,
+    collision_groups: CollisionGroups,
+    include_triggers: bool,
+) -> Intersections {
+    let point: Point<f32> = point.into();
+    intersect_with(
+        ctx,
+        world_id,
+        &Aabb::new(point, point),
+        collision_groups,
+        include_triggers,
+        |shape, isometry| shape.contains_point(isometry, &point),
+    )
+}
+
+/// Returns the bodies, and optionally the triggers, whose bounding box intersects the box
+/// between `mins` and `maxs`. This is cheaper but less precise than [`intersect_shape`].
+pub fn intersect_aabb(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    mins: Vec3,
+    maxs: Vec3,
+    collision_groups: CollisionGroups,
+    include_triggers: bool,
+) -> Intersections {
+    let aabb = Aabb::new(mins.into(), maxs.into());
+    intersect_with(
+        ctx,
+        world_id,
+        &aabb,
+        collision_groups,
+        include_triggers,
+        |shape, isometry| shape.collision_aabb(isometry, 0.0).intersects(&aabb),
+    )
+}
+
+/// Returns the bodies, and optionally the triggers, overlapping a shape, e.g. to find what is
+/// inside an explosion radius.
+pub fn intersect_shape(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    shape: &ShapeWrapper,
+    position: Vec3,
+    rotation: Quat,
+    collision_groups: CollisionGroups,
+    include_triggers: bool,
+) -> Intersections {
+    let shape_isometry = Isometry3::from_parts(position.into(), rotation.into());
+    intersect_with(
+        ctx,
+        world_id,
+        &shape.collision_aabb(&shape_isometry, 0.0),
+        collision_groups,
+        include_triggers,
+        |other, isometry| shape.intersects(&shape_isometry, isometry, other),
+    )
+}
+
+/// Loads the bodies and triggers of a world that can intersect `aabb` and keeps the ones
+/// accepted by `test`, called with their shape and pose.
+fn intersect_with(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    aabb: &Aabb,
+    collision_groups: CollisionGroups,
+    include_triggers: bool,
+    test: impl Fn(&ShapeWrapper, &Isometry3<f32>) -> bool,
+) -> Intersections {
+    let rigid_bodies = bodies_in_aabb(ctx, world_id, aabb)
+        .into_iter()
+        .filter(|body| body.collision_groups().interacts_with(&collision_groups))
+        .filter(|body| test(body.shape(), &body.collider_isometry()))
+        .map(|body| body.id)
+        .collect();
+
+    let triggers = if include_triggers {
+        triggers_in_aabb(ctx, world_id, aabb)
+            .into_iter()
+            .filter(|trigger| trigger.collision_groups.interacts_with(&collision_groups))
+            .filter(|trigger| test(&trigger.shape, &trigger.isometry))
+            .map(|trigger| trigger.trigger_id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Intersections {
+        rigid_bodies,
+        triggers,
+    }
+}
@@ -1,3 +1,14 @@
+mod candidates;
+mod distance;
+mod intersection;
 mod ray;
+mod shape_cast;
 
-pub use ray::raycast_all;
+pub use distance::{
+    closest_body, closest_body_with_rigid_bodies, distance, project_point, PointProjection,
+};
+pub use intersection::{intersect_aabb, intersect_point, intersect_shape, Intersections};
+pub use ray::{raycast_all, raycast_all_filtered};
+pub use shape_cast::{capsule_cast, shape_cast, shape_cast_with_rigid_bodies, sphere_cast};
+
+pub(crate) use shape_cast::cast_against_body;
@@ -3,7 +3,9 @@ use std::fmt::Display;
 use parry3d::query::Ray;
 use spacetimedb::ReducerContext;
 
-use crate::{math::Vec3, Collider, RigidBodyData};
+use crate::{math::Vec3, CollisionGroups, RigidBodyData};
+
+use super::candidates::bodies_along_ray;
 
 #[derive(Debug, Clone)]
 pub struct RacyCastHit {
@@ -31,9 +33,37 @@ pub fn raycast_all(
     max_distance: f32,
     solid: bool,
 ) -> impl Iterator<Item = RacyCastHit> {
-    let colliders = Collider::all(ctx, world_id);
-    let bodies = RigidBodyData::collect(ctx, world_id, &colliders).into_iter();
-    raycast_all_with_rigid_bodies(bodies, origin, direction, max_distance, solid)
+    raycast_all_filtered(
+        ctx,
+        world_id,
+        origin,
+        direction,
+        max_distance,
+        solid,
+        CollisionGroups::ALL,
+    )
+}
+
+/// Same as [`raycast_all`] but only hits the bodies interacting with `collision_groups`.
+pub fn raycast_all_filtered(
+    ctx: &ReducerContext,
+    world_id: u64,
+    origin: Vec3,
+    direction: Vec3,
+    max_distance: f32,
+    solid: bool,
+    collision_groups: CollisionGroups,
+) -> impl Iterator<Item = RacyCastHit> {
+    let ray = Ray::new(origin.into(), direction.into());
+    let bodies = bodies_along_ray(ctx, world_id, &ray, max_distance).into_iter();
+    raycast_all_with_rigid_bodies(
+        bodies,
+        origin,
+        direction,
+        max_distance,
+        solid,
+        collision_groups,
+    )
 }
 
 pub fn raycast_all_with_rigid_bodies(
@@ -42,11 +72,16 @@ pub fn raycast_all_with_rigid_bodies(
     direction: Vec3,
     max_distance: f32,
     solid: bool,
+    collision_groups: CollisionGroups,
 ) -> impl Iterator<Item = RacyCastHit> {
     let ray = Ray::new(origin.into(), direction.into());
 
     entities.filter_map(move |body| {
-        let isometry = (&body).into();
+        if !body.collision_groups().interacts_with(&collision_groups) {
+            return None;
+        }
+
+        let isometry = body.collider_isometry();
 
         body.shape()
             .cast_ray_and_get_normal(&isometry, &ray, max_distance, solid)
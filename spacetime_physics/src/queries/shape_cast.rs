@@ -0,0 +1,195 @@
+use parry3d::na::{Isometry3, Vector3};
+use spacetimedb::ReducerContext;
+
+use crate::{
+    math::{Quat, Vec3},
+    Collider, CollisionGroups, PhysicsWorldId, RigidBodyData, ShapeCastHit, ShapeWrapper,
+};
+
+use super::candidates::bodies_in_aabb;
+
+/// Sweeps a shape from `position` along `direction` and returns every body it hits before
+/// travelling `max_distance`, the closest first. Nothing is hit when `max_distance` is not
+/// finite, as the whole sweep is looked up in the broad phase.
+#[allow(clippy::too_many_arguments)]
+pub fn shape_cast(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    shape: &ShapeWrapper,
+    position: Vec3,
+    rotation: Quat,
+    direction: Vec3,
+    max_distance: f32,
+    collision_groups: CollisionGroups,
+) -> Vec<ShapeCastHit> {
+    if !max_distance.is_finite() {
+        return Vec::new();
+    }
+    let isometry = Isometry3::from_parts(position.into(), rotation.into());
+    let motion: Vector3<f32> = (direction.normalize_or_zero() * max_distance).into();
+    let aabb = shape.swept_aabb(&isometry, &motion);
+    let bodies = bodies_in_aabb(ctx, world_id, &aabb).into_iter();
+    shape_cast_with_rigid_bodies(
+        bodies,
+        shape,
+        position,
+        rotation,
+        direction,
+        max_distance,
+        collision_groups,
+    )
+}
+
+/// Sweeps a sphere from `origin` along `direction`, see [`shape_cast`].
+pub fn sphere_cast(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    radius: f32,
+    origin: Vec3,
+    direction: Vec3,
+    max_distance: f32,
+    collision_groups: CollisionGroups,
+) -> Vec<ShapeCastHit> {
+    let shape = ShapeWrapper::from(&Collider::sphere(world_id, radius));
+    shape_cast(
+        ctx,
+        world_id,
+        &shape,
+        origin,
+        Quat::IDENTITY,
+        direction,
+        max_distance,
+        collision_groups,
+    )
+}
+
+/// Sweeps a capsule, aligned with the Y axis before `rotation` is applied, from `position`
+/// along `direction`, see [`shape_cast`].
+#[allow(clippy::too_many_arguments)]
+pub fn capsule_cast(
+    ctx: &ReducerContext,
+    world_id: PhysicsWorldId,
+    radius: f32,
+    height: f32,
+    position: Vec3,
+    rotation: Quat,
+    direction: Vec3,
+    max_distance: f32,
+    collision_groups: CollisionGroups,
+) -> Vec<ShapeCastHit> {
+    let shape = ShapeWrapper::from(&Collider::capsule(world_id, radius, height));
+    shape_cast(
+        ctx,
+        world_id,
+        &shape,
+        position,
+        rotation,
+        direction,
+        max_distance,
+        collision_groups,
+    )
+}
+
+pub fn shape_cast_with_rigid_bodies(
+    entities: impl Iterator<Item = RigidBodyData>,
+    shape: &ShapeWrapper,
+    position: Vec3,
+    rotation: Quat,
+    direction: Vec3,
+    max_distance: f32,
+    collision_groups: CollisionGroups,
+) -> Vec<ShapeCastHit> {
+    let isometry = Isometry3::from_parts(position.into(), rotation.into());
+    let direction = direction.normalize_or_zero();
+    let velocity: Vector3<f32> = direction.into();
+
+    let mut hits: Vec<_> = entities
+        .filter(|body| body.collision_groups().interacts_with(&collision_groups))
+        .filter_map(|body| cast_against_body(shape, &isometry, &velocity, max_distance, &body))
+        .collect();
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    hits
+}
+
+/// Casts a shape against a single body, `velocity` being the normalized cast direction so
+/// that the time of impact is the travelled distance.
+pub(crate) fn cast_against_body(
+    shape: &ShapeWrapper,
+    isometry: &Isometry3<f32>,
+    velocity: &Vector3<f32>,
+    max_distance: f32,
+    body: &RigidBodyData,
+) -> Option<ShapeCastHit> {
+    let body_isometry = body.collider_isometry();
+    let hit = shape.cast_shape(
+        isometry,
+        velocity,
+        body.shape(),
+        &body_isometry,
+        &Vector3::zeros(),
+        max_distance,
+    )?;
+
+    let mut isometry_at_impact = *isometry;
+    isometry_at_impact.append_translation_mut(&(velocity * hit.time_of_impact).into());
+
+    Some(ShapeCastHit {
+        distance: hit.time_of_impact,
+        witness_shape: (isometry_at_impact * hit.witness1).into(),
+        witness_body: (body_isometry * hit.witness2).into(),
+        normal_shape: (isometry.rotation * hit.normal1.into_inner()).into(),
+        normal_body: (body_isometry.rotation * hit.normal2.into_inner()).into(),
+        rigid_body_id: body.id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sphere_body;
+
+    fn bodies() -> impl Iterator<Item = RigidBodyData> {
+        [6.0, 3.0, 20.0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| sphere_body(i as u64 + 1, Vec3::new(x, 0.0, 0.0)))
+    }
+
+    #[test]
+    fn shape_cast_returns_the_hits_within_max_distance_closest_first() {
+        let shape = ShapeWrapper::from(&Collider::sphere(1, 0.5));
+
+        let hits = shape_cast_with_rigid_bodies(
+            bodies(),
+            &shape,
+            Vec3::ZERO,
+            Quat::IDENTITY,
+            Vec3::new(2.0, 0.0, 0.0),
+            10.0,
+            CollisionGroups::ALL,
+        );
+
+        let ids: Vec<_> = hits.iter().map(|hit| hit.rigid_body_id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert!((hits[0].distance - 2.0).abs() < 1e-3);
+        assert!((hits[0].witness_body - Vec3::new(2.5, 0.0, 0.0)).length() < 1e-3);
+        assert!((hits[0].normal_shape - Vec3::X).length() < 1e-3);
+    }
+
+    #[test]
+    fn shape_cast_skips_filtered_bodies() {
+        let shape = ShapeWrapper::from(&Collider::sphere(1, 0.5));
+
+        let hits = shape_cast_with_rigid_bodies(
+            bodies(),
+            &shape,
+            Vec3::ZERO,
+            Quat::IDENTITY,
+            Vec3::X,
+            10.0,
+            CollisionGroups::NONE,
+        );
+
+        assert!(hits.is_empty());
+    }
+}
@@ -1,8 +1,13 @@
 use std::{collections::HashMap, fmt::Display};
 
+use parry3d::{math::Point, shape::ConvexPolyhedron};
 use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
 
-use crate::math::{Mat3, Vec3};
+use crate::{
+    engine::WorldCache,
+    math::{Mat3, Quat, Vec3},
+    ShapeWrapper,
+};
 
 pub type ColliderId = u64;
 
@@ -16,10 +21,42 @@ pub enum ColliderType {
     Cone,
     Capsule,
     Triangle,
+    /// The convex hull of `points`, e.g. rocks, bevelled crates or vehicle hulls.
+    ConvexHull,
+    /// A triangle mesh made of `points` and `indices`, intended for static level geometry.
+    TriMesh,
+    /// A terrain grid of `rows` by `columns` `heights`, scaled by `size`. Intended for static bodies.
+    Heightfield,
+    /// The union of the `children` colliders, each with its own offset from the body origin.
+    Compound,
+    /// A cuboid with edges rounded by `border_radius`, slides over seams between tiles.
+    RoundCuboid,
+    /// A cylinder with edges rounded by `border_radius`.
+    RoundCylinder,
+    /// A cone with edges rounded by `border_radius`.
+    RoundCone,
+}
+
+/// A shape of a compound collider, placed relative to the origin of the body.
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq)]
+pub struct CompoundChild {
+    pub collider_id: ColliderId,
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+impl CompoundChild {
+    pub fn new(collider_id: ColliderId, position: Vec3, rotation: Quat) -> Self {
+        Self {
+            collider_id,
+            position,
+            rotation,
+        }
+    }
 }
 
 #[table(name = physics_colliders, public)]
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct Collider {
     #[primary_key]
     #[auto_inc]
@@ -29,10 +66,25 @@ pub struct Collider {
     pub radius: f32,
     pub normal: Vec3,
     pub height: f32,
+    /// The size of cuboid colliders, or the scale of heightfield colliders.
     pub size: Vec3,
     pub point_a: Vec3,
     pub point_b: Vec3,
     pub point_c: Vec3,
+    /// The vertices of convex hull and triangle mesh colliders, in the body's local space.
+    pub points: Vec<Vec3>,
+    /// The vertex indices of triangle mesh colliders, three consecutive indices per triangle.
+    pub indices: Vec<u32>,
+    /// The heights of heightfield colliders, row by row.
+    pub heights: Vec<f32>,
+    /// The number of rows of heightfield colliders, along the Z axis.
+    pub rows: u32,
+    /// The number of columns of heightfield colliders, along the X axis.
+    pub columns: u32,
+    /// The shapes of compound colliders, referencing other colliders of the same world.
+    pub children: Vec<CompoundChild>,
+    /// The radius of the rounded edges of round colliders, included in their dimensions.
+    pub border_radius: f32,
     pub collider_type: ColliderType,
 }
 
@@ -55,15 +107,19 @@ impl Collider {
     }
 
     pub fn update(self, ctx: &ReducerContext) -> Self {
+        WorldCache::invalidate(self.world_id);
         ctx.db.physics_colliders().id().update(self)
     }
 
     pub fn delete(&self, ctx: &ReducerContext) {
+        WorldCache::invalidate(self.world_id);
         ctx.db.physics_colliders().id().delete(self.id);
     }
 
     pub fn delete_by_id(ctx: &ReducerContext, id: u64) {
-        ctx.db.physics_colliders().id().delete(id);
+        if let Some(collider) = Self::find(ctx, id) {
+            collider.delete(ctx);
+        }
     }
 
     pub fn id(&mut self, id: u64) -> &mut Self {
@@ -139,20 +195,184 @@ impl Collider {
         }
     }
 
-    pub fn inertia_tensor(&self, mass: f32) -> Mat3 {
+    /// Creates a collider from the convex hull of a point cloud, only the vertices of the hull
+    /// are stored. Returns `None` if the points are degenerate (e.g. all on the same plane).
+    pub fn convex_hull(world_id: u64, points: &[Vec3]) -> Option<Self> {
+        let points: Vec<Point<f32>> = points.iter().map(|&point| point.into()).collect();
+        let hull = ConvexPolyhedron::from_convex_hull(&points)?;
+
+        Some(Self {
+            world_id,
+            points: hull.points().iter().map(|&point| point.into()).collect(),
+            collider_type: ColliderType::ConvexHull,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a triangle mesh collider, meant to be used by static bodies to represent level
+    /// geometry with a single collider instead of one collider per triangle.
+    /// Triangles must reference existing vertices, see [`Collider::has_valid_triangles`].
+    pub fn trimesh(world_id: u64, vertices: Vec<Vec3>, triangles: &[[u32; 3]]) -> Self {
+        Self {
+            world_id,
+            points: vertices,
+            indices: triangles.iter().flatten().copied().collect(),
+            collider_type: ColliderType::TriMesh,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the indices of a triangle mesh collider form whole triangles whose vertices all
+    /// exist. Invalid triangle meshes collide as a point.
+    pub fn has_valid_triangles(&self) -> bool {
+        self.indices.len().is_multiple_of(3)
+            && self
+                .indices
+                .iter()
+                .all(|&index| (index as usize) < self.points.len())
+    }
+
+    /// Returns the triangles of a triangle mesh collider as vertex indices.
+    pub fn triangles(&self) -> Vec<[u32; 3]> {
+        self.indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect()
+    }
+
+    /// Creates a heightfield collider from `rows * columns` heights stored row by row. The grid
+    /// is centered on the body and spans `scale.x` along the X axis and `scale.z` along the Z
+    /// axis, heights are multiplied by `scale.y`.
+    pub fn heightfield(
+        world_id: u64,
+        rows: u32,
+        columns: u32,
+        heights: Vec<f32>,
+        scale: Vec3,
+    ) -> Self {
+        Self {
+            world_id,
+            heights,
+            rows,
+            columns,
+            size: scale,
+            collider_type: ColliderType::Heightfield,
+            ..Default::default()
+        }
+    }
+
+    pub fn round_cuboid(world_id: u64, size: Vec3, border_radius: f32) -> Self {
+        Self {
+            world_id,
+            size,
+            border_radius,
+            collider_type: ColliderType::RoundCuboid,
+            ..Default::default()
+        }
+    }
+
+    pub fn round_cylinder(world_id: u64, radius: f32, height: f32, border_radius: f32) -> Self {
+        Self {
+            world_id,
+            radius,
+            height,
+            border_radius,
+            collider_type: ColliderType::RoundCylinder,
+            ..Default::default()
+        }
+    }
+
+    pub fn round_cone(world_id: u64, radius: f32, height: f32, border_radius: f32) -> Self {
+        Self {
+            world_id,
+            radius,
+            height,
+            border_radius,
+            collider_type: ColliderType::RoundCone,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a collider made of several shapes, e.g. a chair or a car with its wheels. The
+    /// children must be convex shapes, compound colliders can't be nested.
+    pub fn compound(world_id: u64, children: Vec<CompoundChild>) -> Self {
+        Self {
+            world_id,
+            children,
+            collider_type: ColliderType::Compound,
+            ..Default::default()
+        }
+    }
+
+    /// Returns true if the collider can be used as a child of a compound collider.
+    pub fn is_compound_child_supported(&self) -> bool {
+        !matches!(
+            self.collider_type,
+            ColliderType::Plane
+                | ColliderType::TriMesh
+                | ColliderType::Heightfield
+                | ColliderType::Compound
+        )
+    }
+
+    /// Returns a copy of the collider scaled along its local axes. Round sections stay round:
+    /// spheres use the largest scale component while capsules, cylinders and cones scale their
+    /// radius with the largest of X and Z and their height with Y. Border radii use the smallest
+    /// component and compound children only get their offsets and shapes scaled, not rotated.
+    pub fn scaled(&self, scale: Vec3) -> Self {
+        let scale = scale.abs();
+        let radial_scale = scale.x.max(scale.z);
+        let min_scale = scale.x.min(scale.y).min(scale.z);
+
+        let mut collider = self.clone();
         match self.collider_type {
-            ColliderType::Plane => Mat3::ZERO,
-            ColliderType::Sphere => sphere_inertia_tensor(mass, self.radius),
-            ColliderType::Cuboid => cuboid_inertia_tensor(mass, self.size),
-            ColliderType::Cylinder => cylinder_inertia_tensor(mass, self.radius, self.height),
-            ColliderType::Cone => cone_inertia_tensor(mass, self.radius, self.height),
-            ColliderType::Capsule => {
-                capsule_inertia_tensor(mass, self.radius, self.point_a, self.point_b)
+            ColliderType::Sphere => collider.radius *= radial_scale.max(scale.y),
+            // Normals transform with the inverse scale to stay perpendicular to the surface
+            ColliderType::Plane => collider.normal = (self.normal / scale).normalize_or_zero(),
+            ColliderType::Cuboid | ColliderType::Heightfield => collider.size = self.size * scale,
+            ColliderType::RoundCuboid => {
+                collider.size = self.size * scale;
+                collider.border_radius *= min_scale;
+            }
+            ColliderType::Capsule | ColliderType::Cylinder | ColliderType::Cone => {
+                collider.radius *= radial_scale;
+                collider.height *= scale.y;
+            }
+            ColliderType::RoundCylinder | ColliderType::RoundCone => {
+                collider.radius *= radial_scale;
+                collider.height *= scale.y;
+                collider.border_radius *= min_scale;
             }
             ColliderType::Triangle => {
-                triangle_inertia_tensor(mass, self.point_a, self.point_b, self.point_c)
+                collider.point_a = self.point_a * scale;
+                collider.point_b = self.point_b * scale;
+                collider.point_c = self.point_c * scale;
+            }
+            ColliderType::ConvexHull | ColliderType::TriMesh => {
+                collider.points = self.points.iter().map(|&point| point * scale).collect();
+            }
+            ColliderType::Compound => {
+                for child in &mut collider.children {
+                    child.position *= scale;
+                }
             }
         }
+
+        collider
+    }
+
+    /// Returns the inertia tensor around the center of mass of the collider for the given mass,
+    /// computed by parry. The children of compound colliders are not resolved.
+    #[deprecated(
+        note = "use `ShapeWrapper::mass_properties`, which also resolves compound colliders"
+    )]
+    pub fn inertia_tensor(&self, mass: f32) -> Mat3 {
+        let properties = ShapeWrapper::from(self).mass_properties(1.0);
+        if properties.mass <= f32::EPSILON {
+            return Mat3::ZERO;
+        }
+
+        (mass / properties.mass) * properties.inertia_tensor
     }
 }
 
@@ -180,47 +400,86 @@ impl Display for Collider {
                 "Triangle(point_a: {}, point_b: {}, point_c: {})",
                 self.point_a, self.point_b, self.point_c
             ),
+            ColliderType::ConvexHull => write!(f, "ConvexHull(points: {})", self.points.len()),
+            ColliderType::TriMesh => write!(
+                f,
+                "TriMesh(vertices: {}, triangles: {})",
+                self.points.len(),
+                self.indices.len() / 3
+            ),
+            ColliderType::Heightfield => write!(
+                f,
+                "Heightfield(rows: {}, columns: {}, scale: {})",
+                self.rows, self.columns, self.size
+            ),
+            ColliderType::Compound => write!(f, "Compound(children: {})", self.children.len()),
+            ColliderType::RoundCuboid => write!(
+                f,
+                "RoundCuboid(size: {}, border_radius: {})",
+                self.size, self.border_radius
+            ),
+            ColliderType::RoundCylinder => write!(
+                f,
+                "RoundCylinder(radius: {}, height: {}, border_radius: {})",
+                self.radius, self.height, self.border_radius
+            ),
+            ColliderType::RoundCone => write!(
+                f,
+                "RoundCone(radius: {}, height: {}, border_radius: {})",
+                self.radius, self.height, self.border_radius
+            ),
         }
     }
 }
 
-fn cuboid_inertia_tensor(mass: f32, size: Vec3) -> Mat3 {
-    let w = size.x;
-    let h = size.y;
-    let d = size.z;
-    let i_x = (1.0 / 12.0) * mass * (h * h + d * d);
-    let i_y = (1.0 / 12.0) * mass * (w * w + d * d);
-    let i_z = (1.0 / 12.0) * mass * (w * w + h * h);
-    Mat3::from_diagonal(Vec3::new(i_x, i_y, i_z))
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn sphere_inertia_tensor(mass: f32, radius: f32) -> Mat3 {
-    let factor = (2.0 / 5.0) * mass * radius * radius;
-    Mat3::from_diagonal(Vec3::splat(factor))
-}
+    #[test]
+    fn scaled_keeps_round_sections_round() {
+        let scale = Vec3::new(2.0, 3.0, 0.5);
 
-fn cylinder_inertia_tensor(mass: f32, radius: f32, height: f32) -> Mat3 {
-    let i_xz = (1.0 / 12.0) * mass * (3.0 * radius * radius + height * height);
-    let i_y = (1.0 / 2.0) * mass * radius * radius;
-    Mat3::from_diagonal(Vec3::new(i_xz, i_y, i_xz))
-}
+        let sphere = Collider::sphere(1, 1.0).scaled(scale);
+        assert_eq!(sphere.radius, 3.0);
 
-fn cone_inertia_tensor(mass: f32, radius: f32, _height: f32) -> Mat3 {
-    let i_xz = (3.0 / 20.0) * mass * radius * radius;
-    let i_y = (3.0 / 10.0) * mass * radius * radius;
-    Mat3::from_diagonal(Vec3::new(i_xz, i_y, i_xz))
-}
+        let capsule = Collider::capsule(1, 1.0, 2.0).scaled(scale);
+        assert_eq!((capsule.radius, capsule.height), (2.0, 6.0));
 
-fn capsule_inertia_tensor(mass: f32, radius: f32, point_a: Vec3, point_b: Vec3) -> Mat3 {
-    let length = (point_b - point_a).length();
-    let i = (1.0 / 12.0) * mass * (3.0 * radius * radius + length * length);
-    Mat3::from_diagonal(Vec3::new(i, i, i)) // ← approximation
-}
+        let round_cuboid = Collider::round_cuboid(1, Vec3::ONE, 0.2).scaled(scale);
+        assert_eq!(round_cuboid.size, scale);
+        assert_eq!(round_cuboid.border_radius, 0.1);
+    }
 
-fn triangle_inertia_tensor(mass: f32, point_a: Vec3, point_b: Vec3, point_c: Vec3) -> Mat3 {
-    let ab = point_b - point_a;
-    let ac = point_c - point_a;
-    let area = 0.5 * ab.cross(ac).length();
-    let factor = (1.0 / 6.0) * mass * area * area;
-    Mat3::from_diagonal(Vec3::splat(factor))
+    #[test]
+    fn scaled_ignores_the_sign_of_the_scale() {
+        let cuboid = Collider::cuboid(1, Vec3::ONE);
+        assert_eq!(
+            cuboid.scaled(Vec3::new(-2.0, 1.0, 1.0)),
+            cuboid.scaled(Vec3::new(2.0, 1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn scaled_keeps_planes_perpendicular_to_their_surface() {
+        // The plane x + y = 0 becomes x / 2 + y = 0 once stretched twice along X
+        let plane = Collider::plane(1, Vec3::new(1.0, 1.0, 0.0).normalize())
+            .scaled(Vec3::new(2.0, 1.0, 1.0));
+        let expected = Vec3::new(0.5, 1.0, 0.0).normalize();
+        assert!((plane.normal - expected).length() < 1e-6);
+    }
+
+    #[test]
+    fn scaled_moves_compound_children_without_rotating_them() {
+        let rotation = Quat::from_axis_angle(Vec3::Y, 1.0);
+        let compound = Collider::compound(
+            1,
+            vec![CompoundChild::new(2, Vec3::new(1.0, 1.0, 1.0), rotation)],
+        )
+        .scaled(Vec3::new(2.0, 3.0, 4.0));
+
+        assert_eq!(compound.children[0].position, Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(compound.children[0].rotation, rotation);
+        assert_eq!(compound.children[0].collider_id, 2);
+    }
 }
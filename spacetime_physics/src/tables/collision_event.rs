@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
+
+use crate::math::Vec3;
+
+use super::{PhysicsWorldId, RigidBodyId};
+
+pub type CollisionEventId = u64;
+
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionEventKind {
+    /// The bodies started touching during the last step.
+    Started,
+    /// The bodies were already touching during the previous step and still are.
+    Persisted,
+    /// The bodies stopped touching during the last step.
+    Ended,
+}
+
+/// Contacts between rigid bodies during the last step of a world, only recorded when
+/// `PhysicsWorld::collision_events` is enabled. `Started` and `Ended` events are inserted and
+/// last a single step, a `Started` event then becomes `Persisted` and is updated in place as
+/// long as the bodies keep touching, until it is deleted and replaced by an `Ended` event.
+#[table(name = physics_collision_events, public)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+
+    #[index(btree)]
+    pub world_id: u64,
+
+    pub kind: CollisionEventKind,
+
+    /// The body with the smallest id.
+    pub body_a: RigidBodyId,
+
+    pub body_b: RigidBodyId,
+
+    /// The contact point that transmitted the largest impulse, in world space.
+    /// For `Ended` events, the last known contact point.
+    pub point: Vec3,
+
+    /// The contact normal, pointing from body A towards body B.
+    pub normal: Vec3,
+
+    /// The magnitude of the impulse applied between the two bodies during the step, 0.0 for
+    /// `Ended` events and for sleeping bodies.
+    pub impulse: f32,
+}
+
+impl CollisionEvent {
+    pub fn insert(self, ctx: &ReducerContext) -> Self {
+        ctx.db.physics_collision_events().insert(self)
+    }
+
+    pub fn update(self, ctx: &ReducerContext) -> Self {
+        ctx.db.physics_collision_events().id().update(self)
+    }
+
+    pub fn delete(&self, ctx: &ReducerContext) {
+        ctx.db.physics_collision_events().id().delete(self.id);
+    }
+
+    pub fn all(ctx: &ReducerContext, world_id: PhysicsWorldId) -> impl Iterator<Item = Self> {
+        ctx.db
+            .physics_collision_events()
+            .world_id()
+            .filter(world_id)
+    }
+
+    /// Returns the events involving a body.
+    pub fn for_body(
+        ctx: &ReducerContext,
+        world_id: PhysicsWorldId,
+        body_id: RigidBodyId,
+    ) -> impl Iterator<Item = Self> {
+        Self::all(ctx, world_id).filter(move |event| event.involves(body_id))
+    }
+
+    /// Deletes every event of a world.
+    pub fn clear(ctx: &ReducerContext, world_id: PhysicsWorldId) {
+        ctx.db
+            .physics_collision_events()
+            .world_id()
+            .delete(world_id);
+    }
+
+    pub fn involves(&self, body_id: RigidBodyId) -> bool {
+        self.body_a == body_id || self.body_b == body_id
+    }
+
+    /// Returns true if the bodies were touching at the end of the step.
+    pub fn is_touching(&self) -> bool {
+        self.kind != CollisionEventKind::Ended
+    }
+}
+
+impl Display for CollisionEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CollisionEvent(id: {}, world_id: {}, kind: {:?}, body_a: {}, body_b: {}, point: {}, normal: {}, impulse: {})",
+            self.id,
+            self.world_id,
+            self.kind,
+            self.body_a,
+            self.body_b,
+            self.point,
+            self.normal,
+            self.impulse
+        )
+    }
+}
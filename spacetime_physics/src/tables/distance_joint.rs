@@ -0,0 +1,122 @@
+use std::fmt::Display;
+
+use bon::Builder;
+use spacetimedb::{table, ReducerContext, Table};
+
+use crate::math::Vec3;
+
+use super::{RigidBody, RigidBodyId};
+
+pub type DistanceJointId = u64;
+
+/// Keeps the distance between two anchors within `[min_length, max_length]`.
+/// Use `min_length: 0.0` for ropes and tethers, and `min_length == max_length` with a non-zero
+/// compliance for springs.
+#[table(name = physics_distance_joints, public)]
+#[derive(Builder, Debug, Clone, Copy, PartialEq)]
+#[builder(derive(Debug, Clone))]
+pub struct DistanceJoint {
+    /// Unique identifier for the distance joint.
+    #[primary_key]
+    #[auto_inc]
+    #[builder(default = 0)]
+    pub id: u64,
+
+    /// The world this distance joint belongs to.
+    #[index(btree)]
+    #[builder(default = 1)]
+    pub world_id: u64,
+
+    /// The first rigid body attached to the joint.
+    pub body_a: RigidBodyId,
+
+    /// The second rigid body attached to the joint, if `None` body A is attached to the fixed
+    /// world point `anchor_b`.
+    pub body_b: Option<RigidBodyId>,
+
+    /// The attachment point on the first body, in the body's local space.
+    #[builder(default = Vec3::ZERO)]
+    pub local_anchor_a: Vec3,
+
+    /// The attachment point on the second body in the body's local space, or in world space if
+    /// there is no second body.
+    #[builder(default = Vec3::ZERO)]
+    pub anchor_b: Vec3,
+
+    /// The minimum distance between the two anchors.
+    #[builder(default = 0.0)]
+    pub min_length: f32,
+
+    /// The maximum distance between the two anchors.
+    pub max_length: f32,
+
+    /// The compliance (inverse of stiffness) of the joint. A value of 0.0 makes the joint
+    /// perfectly rigid, higher values make it behave like a spring.
+    #[builder(default = 0.0)]
+    pub compliance: f32,
+
+    /// How fast oscillations of the spring die out. Only has an effect when `compliance` is
+    /// greater than 0.0.
+    #[builder(default = 0.0)]
+    pub damping: f32,
+}
+
+impl DistanceJoint {
+    pub fn insert(self, ctx: &ReducerContext) -> Self {
+        self.wake_up_bodies(ctx);
+        ctx.db.physics_distance_joints().insert(self)
+    }
+
+    pub fn find(ctx: &ReducerContext, id: u64) -> Option<Self> {
+        ctx.db.physics_distance_joints().id().find(id)
+    }
+
+    /// Writes the joint to the database and wakes up the bodies it was and is now attached to.
+    pub fn update(self, ctx: &ReducerContext) -> Self {
+        if let Some(joint) = Self::find(ctx, self.id) {
+            joint.wake_up_bodies(ctx);
+        }
+        self.wake_up_bodies(ctx);
+        ctx.db.physics_distance_joints().id().update(self)
+    }
+
+    pub fn delete(&self, ctx: &ReducerContext) {
+        self.wake_up_bodies(ctx);
+        ctx.db.physics_distance_joints().id().delete(self.id);
+    }
+
+    pub fn delete_by_id(ctx: &ReducerContext, id: u64) {
+        if let Some(joint) = Self::find(ctx, id) {
+            joint.delete(ctx);
+        }
+    }
+
+    pub fn all(ctx: &ReducerContext, world_id: u64) -> impl Iterator<Item = Self> {
+        ctx.db.physics_distance_joints().world_id().filter(world_id)
+    }
+
+    /// Sleeping bodies are not simulated, wake them up so that they react to the joint change.
+    fn wake_up_bodies(&self, ctx: &ReducerContext) {
+        RigidBody::wake_up_by_id(ctx, self.body_a);
+        if let Some(body_b) = self.body_b {
+            RigidBody::wake_up_by_id(ctx, body_b);
+        }
+    }
+}
+
+impl Display for DistanceJoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DistanceJoint(id: {}, world_id: {}, body_a: {}, body_b: {:?}, min_length: {}, max_length: {}, compliance: {}, damping: {})",
+            self.id,
+            self.world_id,
+            self.body_a,
+            self.body_b,
+            self.min_length,
+            self.max_length,
+            self.compliance,
+            self.damping
+        )
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt::Display;
+
+use bon::Builder;
+use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
+
+use crate::math::{Quat, Vec3};
+
+use super::{RigidBody, RigidBodyId};
+
+pub type JointId = u64;
+
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Default)]
+pub enum JointType {
+    /// Locks all relative translations and rotations between the two bodies.
+    #[default]
+    Fixed,
+    /// Allows the bodies to rotate around a single shared axis, e.g. doors, wheels or turrets.
+    Revolute,
+    /// Allows the bodies to translate along a single shared axis, e.g. sliders or pistons.
+    Prismatic,
+    /// Allows free rotation around the anchor point (ball-and-socket), e.g. chains or ragdolls.
+    Spherical,
+}
+
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Default)]
+pub enum JointMotor {
+    #[default]
+    Disabled,
+    /// Drives the joint at `motor_target` speed, in radians per second for revolute joints and
+    /// units per second for prismatic joints.
+    Velocity,
+    /// Drives the joint towards `motor_target`, an angle in radians for revolute joints and a
+    /// distance along the axis for prismatic joints.
+    Position,
+}
+
+#[table(name = physics_joints, public)]
+#[derive(Builder, Debug, Clone, Copy, PartialEq)]
+#[builder(derive(Debug, Clone))]
+pub struct Joint {
+    /// Unique identifier for the joint.
+    #[primary_key]
+    #[auto_inc]
+    #[builder(default = 0)]
+    pub id: u64,
+
+    /// The world this joint belongs to.
+    #[index(btree)]
+    #[builder(default = 1)]
+    pub world_id: u64,
+
+    /// The kind of motion allowed between the two bodies.
+    #[builder(default = JointType::default())]
+    pub joint_type: JointType,
+
+    /// The first rigid body connected by the joint.
+    pub body_a: RigidBodyId,
+
+    /// The second rigid body connected by the joint.
+    pub body_b: RigidBodyId,
+
+    /// The attachment point on the first body, in the body's local space.
+    #[builder(default = Vec3::ZERO)]
+    pub local_anchor_a: Vec3,
+
+    /// The attachment point on the second body, in the body's local space.
+    #[builder(default = Vec3::ZERO)]
+    pub local_anchor_b: Vec3,
+
+    /// The rotation (revolute) or translation (prismatic) axis in the first body's local space.
+    #[builder(default = Vec3::Y)]
+    pub local_axis_a: Vec3,
+
+    /// The rotation (revolute) or translation (prismatic) axis in the second body's local space.
+    #[builder(default = Vec3::Y)]
+    pub local_axis_b: Vec3,
+
+    /// The rotation of the second body relative to the first one that fixed and prismatic
+    /// joints try to maintain, and at which the angle of revolute joints is zero.
+    #[builder(default = Quat::IDENTITY)]
+    pub rest_rotation: Quat,
+
+    /// The compliance (inverse of stiffness) of the positional part of the joint.
+    /// A value of 0.0 makes the joint perfectly rigid.
+    #[builder(default = 0.0)]
+    pub compliance: f32,
+
+    /// The compliance (inverse of stiffness) of the angular part of the joint.
+    /// A value of 0.0 makes the joint perfectly rigid.
+    #[builder(default = 0.0)]
+    pub angular_compliance: f32,
+
+    /// Whether `lower_limit` and `upper_limit` are enforced. Only used by revolute and prismatic joints.
+    #[builder(default = false)]
+    pub limits_enabled: bool,
+
+    /// The minimum angle (revolute, in radians) or distance along the axis (prismatic) of the joint.
+    #[builder(default = 0.0)]
+    pub lower_limit: f32,
+
+    /// The maximum angle (revolute, in radians) or distance along the axis (prismatic) of the joint.
+    #[builder(default = 0.0)]
+    pub upper_limit: f32,
+
+    /// The motor driving the joint. Only used by revolute and prismatic joints.
+    #[builder(default = JointMotor::default())]
+    pub motor: JointMotor,
+
+    /// The target velocity or position of the motor, see [`JointMotor`].
+    #[builder(default = 0.0)]
+    pub motor_target: f32,
+
+    /// The compliance (inverse of stiffness) of the motor. A value of 0.0 reaches the target
+    /// in a single step if `motor_max_force` allows it.
+    #[builder(default = 0.0)]
+    pub motor_compliance: f32,
+
+    /// The maximum force (or torque for revolute joints) the motor can apply.
+    #[builder(default = f32::MAX)]
+    pub motor_max_force: f32,
+
+    /// The force transmitted through the anchors above which the joint breaks.
+    #[builder(default = f32::MAX)]
+    pub break_force: f32,
+
+    /// The torque transmitted through the angular part of the joint above which it breaks.
+    #[builder(default = f32::MAX)]
+    pub break_torque: f32,
+
+    /// Whether the joint has been broken. Broken joints are ignored by the solver.
+    #[builder(skip = false)]
+    pub broken: bool,
+
+    /// The force that was measured when the joint broke.
+    #[builder(skip = 0.0)]
+    pub broken_force: f32,
+
+    /// The torque that was measured when the joint broke.
+    #[builder(skip = 0.0)]
+    pub broken_torque: f32,
+}
+
+impl Joint {
+    pub fn insert(self, ctx: &ReducerContext) -> Self {
+        self.wake_up_bodies(ctx);
+        ctx.db.physics_joints().insert(self)
+    }
+
+    pub fn find(ctx: &ReducerContext, id: u64) -> Option<Self> {
+        ctx.db.physics_joints().id().find(id)
+    }
+
+    /// Writes the joint to the database and wakes up the bodies it was and is now attached to.
+    pub fn update(self, ctx: &ReducerContext) -> Self {
+        if let Some(joint) = Self::find(ctx, self.id) {
+            joint.wake_up_bodies(ctx);
+        }
+        self.wake_up_bodies(ctx);
+        ctx.db.physics_joints().id().update(self)
+    }
+
+    /// Writes the state computed by the physics step without waking the bodies up.
+    pub(crate) fn update_from_simulation(self, ctx: &ReducerContext) -> Self {
+        ctx.db.physics_joints().id().update(self)
+    }
+
+    pub fn delete(&self, ctx: &ReducerContext) {
+        self.wake_up_bodies(ctx);
+        ctx.db.physics_joints().id().delete(self.id);
+    }
+
+    pub fn delete_by_id(ctx: &ReducerContext, id: u64) {
+        if let Some(joint) = Self::find(ctx, id) {
+            joint.delete(ctx);
+        }
+    }
+
+    pub fn all(ctx: &ReducerContext, world_id: u64) -> impl Iterator<Item = Self> {
+        ctx.db.physics_joints().world_id().filter(world_id)
+    }
+
+    pub fn has_limits(&self) -> bool {
+        self.limits_enabled && matches!(self.joint_type, JointType::Revolute | JointType::Prismatic)
+    }
+
+    pub fn has_motor(&self) -> bool {
+        self.motor != JointMotor::Disabled
+            && matches!(self.joint_type, JointType::Revolute | JointType::Prismatic)
+    }
+
+    /// Sleeping bodies are not simulated, wake them up so that they react to the joint change.
+    fn wake_up_bodies(&self, ctx: &ReducerContext) {
+        RigidBody::wake_up_by_id(ctx, self.body_a);
+        RigidBody::wake_up_by_id(ctx, self.body_b);
+    }
+}
+
+impl Display for Joint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Joint(id: {}, world_id: {}, type: {:?}, body_a: {}, body_b: {}, anchor_a: {}, anchor_b: {}, broken: {})",
+            self.id,
+            self.world_id,
+            self.joint_type,
+            self.body_a,
+            self.body_b,
+            self.local_anchor_a,
+            self.local_anchor_b,
+            self.broken
+        )
+    }
+}
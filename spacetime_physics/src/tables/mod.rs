@@ -1,13 +1,21 @@
 pub mod colliders;
+pub mod collision_event;
+pub mod distance_joint;
+pub mod joint;
 pub mod physics_world;
 pub mod raycast;
 pub mod rigid_body;
 pub mod rigid_body_properties;
+pub mod shape_cast;
 pub mod trigger;
 
 pub use colliders::*;
+pub use collision_event::*;
+pub use distance_joint::*;
+pub use joint::*;
 pub use physics_world::*;
 pub use raycast::*;
 pub use rigid_body::*;
 pub use rigid_body_properties::*;
+pub use shape_cast::*;
 pub use trigger::*;
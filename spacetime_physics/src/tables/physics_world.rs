@@ -1,6 +1,6 @@
 use std::{fmt::Display, time::Duration};
 
-use bon::{builder, Builder};
+use bon::Builder;
 use spacetimedb::{table, ReducerContext, ScheduleAt, Table};
 
 use crate::{math::Vec3, utils::LogStopwatch};
@@ -54,9 +54,10 @@ pub struct PhysicsWorld {
     pub position_iterations: u32,
 
     /// The dilation factor for the QBVH (Quantized Bounding Volume Hierarchy) used for collision detection.
-    /// This factor determines how much the bounding volumes are expanded to account for movement
-    /// and ensure that fast-moving objects are still detected for collisions.
-    #[builder(default = 0.001)]
+    /// This is how much the bounding boxes stored in the broad phase are enlarged, relative to the
+    /// length unit. Bodies moving less than this margin since their last update are not reinserted
+    /// in the QBVH, larger values mean fewer updates but more pairs to check during the narrow phase.
+    #[builder(default = 0.1)]
     pub qvbh_dilation_factor: f32,
 
     /// How many units are in one meter in the physics world. This is used to convert between
@@ -69,6 +70,55 @@ pub struct PhysicsWorld {
     #[builder(default = 0.002)]
     pub normalized_prediction_distance: f32,
 
+    /// The fraction of the contact impulses of the previous substep that is applied before
+    /// solving the contacts. Higher values make stacks and resting bodies more stable, 0.0
+    /// disables warm starting.
+    #[builder(default = 0.8)]
+    pub warm_start_coefficient: f32,
+
+    /// If true, the contacts between rigid bodies are recorded every step in the
+    /// `physics_collision_events` table.
+    #[builder(default = false)]
+    pub collision_events: bool,
+
+    /// If true, dynamic bodies that have been resting for `time_to_sleep` seconds are put to
+    /// sleep and skipped by the simulation until something wakes them up.
+    #[builder(default = true)]
+    pub sleeping_enabled: bool,
+
+    /// The linear velocity, in units per second, under which a body is considered resting.
+    #[builder(default = 0.1)]
+    pub sleep_linear_threshold: f32,
+
+    /// The angular velocity, in radians per second, under which a body is considered resting.
+    #[builder(default = 0.1)]
+    pub sleep_angular_threshold: f32,
+
+    /// How long, in seconds, every body of an island must be resting before the island falls asleep.
+    #[builder(default = 0.5)]
+    pub time_to_sleep: f32,
+
+    /// The distance, in units, a body must move from its last written position before its new
+    /// position is written to the database. Smaller movements are kept in memory and accumulate
+    /// until they exceed it, avoiding updates for jittering bodies.
+    #[builder(default = 1e-4)]
+    pub position_write_epsilon: f32,
+
+    /// The angle, in radians, a body must rotate before its new rotation is written to the database.
+    #[builder(default = 1e-4)]
+    pub rotation_write_epsilon: f32,
+
+    /// The change in linear (units per second) or angular (radians per second) velocity above
+    /// which a body is written to the database even if it did not move.
+    #[builder(default = 1e-3)]
+    pub velocity_write_epsilon: f32,
+
+    /// The number of steps simulated so far, incremented by `step_world`. As it is rolled back
+    /// along with the rest of a failed reducer, it is used to detect when the state the engine
+    /// keeps in memory between steps no longer matches the database.
+    #[builder(default = 0)]
+    pub step_count: u64,
+
     /// If true, the physics world will log detailed debug information to the console. This is very
     /// verbose and should only be used for debugging purposes.
     #[builder(default = false)]
@@ -94,7 +144,7 @@ pub struct PhysicsWorld {
     #[builder(default = false)]
     pub debug_broad_narrow_phase: bool,
 
-    /// If true, the physics world will log the raycasts hits to the console.
+    /// If true, the physics world will log the raycasts and shape casts hits to the console.
     #[builder(default = false)]
     pub debug_raycasts: bool,
 
@@ -132,6 +182,10 @@ impl PhysicsWorld {
         self.normalized_prediction_distance * self.length_unit
     }
 
+    pub fn broad_phase_margin(&self) -> f32 {
+        self.qvbh_dilation_factor * self.length_unit
+    }
+
     pub fn debug_broad_phase(&self) -> bool {
         self.debug || self.debug_broad_phase || self.debug_broad_narrow_phase
     }
@@ -1,10 +1,10 @@
 use core::f32;
 use std::hash::Hash;
 
-use bon::{builder, Builder};
+use bon::Builder;
 use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
 
-use crate::math::Vec3;
+use crate::{math::Vec3, CollisionGroups};
 
 use super::RigidBodyId;
 
@@ -75,6 +75,10 @@ pub struct RayCast {
     #[builder(default = false)]
     pub solid: bool,
 
+    /// Filters which bodies the raycast can hit.
+    #[builder(default = CollisionGroups::ALL)]
+    pub collision_groups: CollisionGroups,
+
     /// The entities currently intersecting the raycast.
     pub hits: Vec<RayCastHit>,
 
@@ -100,6 +104,7 @@ impl RayCast {
             direction: direction.normalize(),
             max_distance,
             solid,
+            collision_groups: CollisionGroups::ALL,
             hits: Vec::new(),
             added_hits: Vec::new(),
             removed_hits: Vec::new(),
@@ -1,10 +1,14 @@
 use std::fmt::Display;
 
-use bon::{builder, Builder};
+use bon::Builder;
 use parry3d::na::Isometry3;
 use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
 
-use crate::math::{Quat, Vec3};
+use crate::{
+    engine::WorldCache,
+    math::{Mat3, Quat, Vec3},
+    CollisionGroups,
+};
 
 pub type RigidBodyId = u64;
 
@@ -16,6 +20,16 @@ pub enum RigidBodyType {
     Kinematic,
 }
 
+/// The mass properties of a body as computed by the engine from its collider and properties,
+/// in the body's local space. Read only, they are overwritten at every step.
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ComputedMassProperties {
+    pub mass: f32,
+    pub center_of_mass: Vec3,
+    /// The inertia tensor around the center of mass.
+    pub inertia_tensor: Mat3,
+}
+
 #[table(name = physics_rigid_bodies, public)]
 #[derive(Builder, Clone, Copy, Debug, Default, PartialEq)]
 #[builder(derive(Debug, Clone))]
@@ -47,11 +61,50 @@ pub struct RigidBody {
 
     pub collider_id: u64,
     pub properties_id: u64,
+
+    /// The position of the collider relative to the body origin, e.g. to put the feet of a
+    /// capsule at the origin.
+    #[builder(default = Vec3::ZERO)]
+    pub collider_position: Vec3,
+
+    /// The rotation of the collider relative to the body.
+    #[builder(default = Quat::IDENTITY)]
+    pub collider_rotation: Quat,
+
+    /// Scales the collider along its local axes, allowing bodies of different sizes to share
+    /// the same collider. The mass properties are computed from the scaled shape.
+    #[builder(default = Vec3::ONE)]
+    pub scale: Vec3,
+
+    /// Filters which bodies, triggers and raycasts this body interacts with.
+    #[builder(default = CollisionGroups::ALL)]
+    pub collision_groups: CollisionGroups,
+
+    /// Enables continuous collision detection, preventing fast bodies (e.g. projectiles) from
+    /// tunneling through thin colliders at the cost of extra shape casts.
+    #[builder(default = false)]
+    pub ccd: bool,
+
+    /// Sleeping bodies are not simulated until they are woken up, either by an awake body
+    /// touching them or by being written to through [`RigidBody::update`].
+    #[builder(default = false)]
+    pub sleeping: bool,
+
+    /// How long the body has been moving slower than the world's sleep thresholds, in seconds.
+    #[builder(skip = 0.0)]
+    pub sleep_timer: f32,
+
+    /// The mass properties used by the last step, for inspection purposes. Always zero for
+    /// static bodies, which are never moved by the solver.
+    #[builder(skip)]
+    pub mass_properties: ComputedMassProperties,
 }
 
 impl RigidBody {
     pub fn insert(self, ctx: &ReducerContext) -> Self {
-        ctx.db.physics_rigid_bodies().insert(self)
+        let rigid_body = ctx.db.physics_rigid_bodies().insert(self);
+        WorldCache::invalidate_body(rigid_body.world_id, rigid_body.id);
+        rigid_body
     }
 
     pub fn find(ctx: &ReducerContext, id: u64) -> Option<Self> {
@@ -62,16 +115,28 @@ impl RigidBody {
         ctx.db.physics_rigid_bodies().world_id().filter(world_id)
     }
 
-    pub fn update(self, ctx: &ReducerContext) -> Self {
+    /// Writes the body to the database and wakes it up so that the changes (e.g. a new
+    /// velocity or position) are taken into account by the next physics step.
+    pub fn update(mut self, ctx: &ReducerContext) -> Self {
+        self.wake_up();
+        WorldCache::invalidate_body(self.world_id, self.id);
+        ctx.db.physics_rigid_bodies().id().update(self)
+    }
+
+    /// Writes the state computed by the physics step without waking the body up.
+    pub(crate) fn update_from_simulation(self, ctx: &ReducerContext) -> Self {
         ctx.db.physics_rigid_bodies().id().update(self)
     }
 
     pub fn delete(&self, ctx: &ReducerContext) {
+        WorldCache::invalidate_body(self.world_id, self.id);
         ctx.db.physics_rigid_bodies().id().delete(self.id);
     }
 
     pub fn delte_by_id(ctx: &ReducerContext, id: u64) {
-        ctx.db.physics_rigid_bodies().id().delete(id);
+        if let Some(rigid_body) = Self::find(ctx, id) {
+            rigid_body.delete(ctx);
+        }
     }
 
     pub fn is_dynamic(&self) -> bool {
@@ -81,14 +146,26 @@ impl RigidBody {
     pub fn is_kinematic(&self) -> bool {
         self.body_type == RigidBodyType::Kinematic
     }
+
+    pub fn wake_up(&mut self) {
+        self.sleeping = false;
+        self.sleep_timer = 0.0;
+    }
+
+    /// Wakes the body up if it is sleeping, e.g. when a joint attached to it changes.
+    pub(crate) fn wake_up_by_id(ctx: &ReducerContext, id: RigidBodyId) {
+        if let Some(rigid_body) = Self::find(ctx, id).filter(|rigid_body| rigid_body.sleeping) {
+            rigid_body.update(ctx);
+        }
+    }
 }
 
 impl Display for RigidBody {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "RigidBody(id={}, world_id={}, position={}, orientation={}, velocity={}, force={})",
-            self.id, self.world_id, self.position, self.rotation, self.linear_velocity, self.force
+            "RigidBody(id={}, world_id={}, position={}, orientation={}, velocity={}, force={}, sleeping={})",
+            self.id, self.world_id, self.position, self.rotation, self.linear_velocity, self.force, self.sleeping
         )
     }
 }
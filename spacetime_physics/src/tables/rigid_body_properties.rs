@@ -1,10 +1,90 @@
 use std::fmt::Display;
 
-use bon::{builder, Builder};
-use spacetimedb::{table, ReducerContext, Table};
+use bon::Builder;
+use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
 
 use crate::math::Vec3;
 
+/// Prevents bodies from translating or rotating along specific world axes, e.g. locking all
+/// rotations of a player capsule or the Z translation of bodies in a 2.5D game.
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq, Default)]
+pub struct LockedAxes {
+    pub translation_x: bool,
+    pub translation_y: bool,
+    pub translation_z: bool,
+    pub rotation_x: bool,
+    pub rotation_y: bool,
+    pub rotation_z: bool,
+}
+
+impl LockedAxes {
+    pub const NONE: Self = Self {
+        translation_x: false,
+        translation_y: false,
+        translation_z: false,
+        rotation_x: false,
+        rotation_y: false,
+        rotation_z: false,
+    };
+
+    pub const TRANSLATION_LOCKED: Self = Self {
+        translation_x: true,
+        translation_y: true,
+        translation_z: true,
+        ..Self::NONE
+    };
+
+    pub const ROTATION_LOCKED: Self = Self {
+        rotation_x: true,
+        rotation_y: true,
+        rotation_z: true,
+        ..Self::NONE
+    };
+
+    pub const ALL_LOCKED: Self = Self {
+        translation_x: true,
+        translation_y: true,
+        translation_z: true,
+        rotation_x: true,
+        rotation_y: true,
+        rotation_z: true,
+    };
+
+    /// Returns a vector with 0.0 for the locked translation axes and 1.0 for the free ones.
+    pub fn translation_mask(&self) -> Vec3 {
+        Vec3::new(
+            axis_mask(self.translation_x),
+            axis_mask(self.translation_y),
+            axis_mask(self.translation_z),
+        )
+    }
+
+    /// Returns a vector with 0.0 for the locked rotation axes and 1.0 for the free ones.
+    pub fn rotation_mask(&self) -> Vec3 {
+        Vec3::new(
+            axis_mask(self.rotation_x),
+            axis_mask(self.rotation_y),
+            axis_mask(self.rotation_z),
+        )
+    }
+
+    pub fn is_translation_locked(&self) -> bool {
+        self.translation_x || self.translation_y || self.translation_z
+    }
+
+    pub fn is_rotation_locked(&self) -> bool {
+        self.rotation_x || self.rotation_y || self.rotation_z
+    }
+}
+
+fn axis_mask(locked: bool) -> f32 {
+    if locked {
+        0.0
+    } else {
+        1.0
+    }
+}
+
 #[table(name = physics_rigid_body_properties, public)]
 #[derive(Builder, Debug, Clone, PartialEq)]
 pub struct RigidBodyProperties {
@@ -21,10 +101,25 @@ pub struct RigidBodyProperties {
     pub friction_dynamic_coefficient: f32,
     #[builder(default = 0.0)]
     pub restitution_coefficient: f32,
+    /// How quickly the linear velocity decays over time, e.g. air resistance. 0.0 means no damping.
+    #[builder(default = 0.0)]
+    pub linear_damping: f32,
+    /// How quickly the angular velocity decays over time. 0.0 means no damping.
+    #[builder(default = 0.0)]
+    pub angular_damping: f32,
+    /// The world axes along which bodies using these properties can't translate or rotate.
+    #[builder(default = LockedAxes::NONE)]
+    pub locked_axes: LockedAxes,
     #[builder(default = 1.0)]
     pub mass: f32,
-    #[builder(skip = if mass > 0.0 { 1.0 / mass } else { 0.0 })]
-    pub inv_mass: f32,
+
+    /// When set, the mass of each body is computed from the volume of its collider and `mass`
+    /// is ignored. Shapes without volume (planes, triangles, heightfields) end up without mass.
+    pub density: Option<f32>,
+
+    /// Overrides the center of mass computed from the collider, in the body's local space,
+    /// e.g. to lower the center of mass of a vehicle. The inertia is kept unchanged.
+    pub center_of_mass: Option<Vec3>,
 }
 
 impl RigidBodyProperties {
@@ -67,8 +162,25 @@ impl RigidBodyProperties {
         (self.restitution_coefficient + other.restitution_coefficient) / 2.0
     }
 
+    /// Returns the inverse of `mass`, ignoring `density` and the collider of the bodies.
+    #[deprecated(
+        note = "the mass of a body depends on its collider, use `RigidBody::mass_properties`"
+    )]
+    pub fn inv_mass(&self) -> f32 {
+        if self.mass > 0.0 {
+            1.0 / self.mass
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the inverse of `mass` along each axis, zero along the locked translation axes.
+    #[deprecated(
+        note = "the mass of a body depends on its collider, use `RigidBody::mass_properties`"
+    )]
+    #[allow(deprecated)]
     pub fn effective_inverse_mass(&self) -> Vec3 {
-        Vec3::splat(self.inv_mass)
+        Vec3::splat(self.inv_mass()) * self.locked_axes.translation_mask()
     }
 }
 
@@ -76,14 +188,18 @@ impl Display for RigidBodyProperties {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "RigidBodyProperties {{ id: {}, world_id: {}, friction_static_coefficient: {}, friction_dynamic_coefficient: {}, restitution_coefficient: {}, mass: {}, inv_mass: {} }}",
+            "RigidBodyProperties {{ id: {}, world_id: {}, friction_static_coefficient: {}, friction_dynamic_coefficient: {}, restitution_coefficient: {}, linear_damping: {}, angular_damping: {}, locked_axes: {:?}, mass: {}, density: {:?}, center_of_mass: {:?} }}",
             self.id,
             self.world_id,
             self.friction_static_coefficient,
             self.friction_dynamic_coefficient,
             self.restitution_coefficient,
+            self.linear_damping,
+            self.angular_damping,
+            self.locked_axes,
             self.mass,
-            self.inv_mass
+            self.density,
+            self.center_of_mass
         )
     }
 }
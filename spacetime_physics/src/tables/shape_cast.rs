@@ -0,0 +1,173 @@
+use std::{fmt::Display, hash::Hash};
+
+use bon::Builder;
+use spacetimedb::{table, ReducerContext, SpacetimeType, Table};
+
+use crate::{
+    math::{Quat, Vec3},
+    ColliderId, CollisionGroups,
+};
+
+use super::RigidBodyId;
+
+pub type ShapeCastId = u64;
+
+/// A rigid body hit by a shape swept along a direction.
+#[derive(SpacetimeType, Debug, Clone, Copy)]
+pub struct ShapeCastHit {
+    /// The distance travelled by the cast shape before touching the body, 0.0 if they were
+    /// already overlapping.
+    pub distance: f32,
+
+    /// The contact point on the cast shape at the time of impact, in world coordinates.
+    pub witness_shape: Vec3,
+
+    /// The contact point on the body, in world coordinates.
+    pub witness_body: Vec3,
+
+    /// The normal at the contact point on the cast shape, in world coordinates.
+    pub normal_shape: Vec3,
+
+    /// The normal at the contact point on the body, pointing away from its surface.
+    pub normal_body: Vec3,
+
+    /// The ID of the rigid body that was hit.
+    pub rigid_body_id: RigidBodyId,
+}
+
+impl Display for ShapeCastHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ShapeCastHit {{ distance: {}, witness_shape: {}, witness_body: {}, normal_shape: {}, normal_body: {}, rigid_body_id: {} }}",
+            self.distance,
+            self.witness_shape,
+            self.witness_body,
+            self.normal_shape,
+            self.normal_body,
+            self.rigid_body_id
+        )
+    }
+}
+
+impl Hash for ShapeCastHit {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.distance.to_bits().hash(state);
+        self.witness_shape.hash(state);
+        self.witness_body.hash(state);
+        self.normal_shape.hash(state);
+        self.normal_body.hash(state);
+        self.rigid_body_id.hash(state);
+    }
+}
+
+impl Eq for ShapeCastHit {}
+
+impl PartialEq for ShapeCastHit {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance.to_bits() == other.distance.to_bits()
+            && self.witness_shape == other.witness_shape
+            && self.witness_body == other.witness_body
+            && self.normal_shape == other.normal_shape
+            && self.normal_body == other.normal_body
+            && self.rigid_body_id == other.rigid_body_id
+    }
+}
+
+/// A shape swept along a direction every tick, the thick counterpart of [`RayCast`](super::RayCast)
+/// for melee swings, vision cones, etc.
+#[table(name = physics_shapecasts)]
+#[derive(Builder, Debug, Clone, PartialEq)]
+pub struct ShapeCast {
+    /// Unique identifier for the shape cast.
+    #[primary_key]
+    #[auto_inc]
+    #[builder(default = 0)]
+    pub id: u64,
+
+    /// The world this shape cast belongs to.
+    #[index(btree)]
+    #[builder(default = 1)]
+    pub world_id: u64,
+
+    /// The position the shape starts from, in world coordinates.
+    pub origin: Vec3,
+
+    /// The rotation of the shape during the whole sweep.
+    #[builder(default = Quat::IDENTITY)]
+    pub rotation: Quat,
+
+    /// The direction of the sweep, normalized to unit length.
+    pub direction: Vec3,
+
+    /// The maximum distance the shape can travel, required as the broad phase queries the whole
+    /// sweep. Shape casts with a non-finite or negative distance are skipped.
+    pub max_distance: f32,
+
+    /// The collider used as the swept shape.
+    pub collider_id: ColliderId,
+
+    /// Filters which bodies the shape cast can hit.
+    #[builder(default = CollisionGroups::ALL)]
+    pub collision_groups: CollisionGroups,
+
+    /// The bodies currently hit by the shape, the closest first.
+    #[builder(default = Vec::new())]
+    pub hits: Vec<ShapeCastHit>,
+
+    /// The hits that were added to the shape cast since the last update.
+    #[builder(default = Vec::new())]
+    pub added_hits: Vec<ShapeCastHit>,
+
+    /// The hits that were removed from the shape cast since the last update.
+    #[builder(default = Vec::new())]
+    pub removed_hits: Vec<ShapeCastHit>,
+}
+
+impl ShapeCast {
+    pub fn new(
+        world_id: u64,
+        collider_id: ColliderId,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+    ) -> Self {
+        Self {
+            id: 0,
+            world_id,
+            origin,
+            rotation: Quat::IDENTITY,
+            direction: direction.normalize_or_zero(),
+            max_distance,
+            collider_id,
+            collision_groups: CollisionGroups::ALL,
+            hits: Vec::new(),
+            added_hits: Vec::new(),
+            removed_hits: Vec::new(),
+        }
+    }
+
+    pub fn insert(self, ctx: &ReducerContext) -> Self {
+        ctx.db.physics_shapecasts().insert(self)
+    }
+
+    pub fn find(ctx: &ReducerContext, id: ShapeCastId) -> Option<Self> {
+        ctx.db.physics_shapecasts().id().find(id)
+    }
+
+    pub fn update(self, ctx: &ReducerContext) -> Self {
+        ctx.db.physics_shapecasts().id().update(self)
+    }
+
+    pub fn delete(self, ctx: &ReducerContext) {
+        ctx.db.physics_shapecasts().id().delete(self.id);
+    }
+
+    pub fn delete_by_id(ctx: &ReducerContext, id: ShapeCastId) {
+        ctx.db.physics_shapecasts().id().delete(id);
+    }
+
+    pub fn all(ctx: &ReducerContext, world_id: u64) -> impl Iterator<Item = Self> {
+        ctx.db.physics_shapecasts().world_id().filter(world_id)
+    }
+}
@@ -1,10 +1,14 @@
 use std::fmt::Display;
 
-use bon::{builder, Builder};
+use bon::Builder;
 use parry3d::na::Isometry3;
 use spacetimedb::{table, ReducerContext, Table};
 
-use crate::math::{Quat, Vec3};
+use crate::{
+    engine::WorldCache,
+    math::{Quat, Vec3},
+    CollisionGroups,
+};
 
 use super::RigidBodyId;
 
@@ -28,6 +32,15 @@ pub struct Trigger {
 
     pub collider_id: u64,
 
+    /// Scales the collider along its local axes, allowing triggers of different sizes to share
+    /// the same collider.
+    #[builder(default = Vec3::ONE)]
+    pub scale: Vec3,
+
+    /// Filters which bodies the trigger detects.
+    #[builder(default = CollisionGroups::ALL)]
+    pub collision_groups: CollisionGroups,
+
     /// The entities currently inside the trigger.
     #[builder(default = Vec::new())]
     pub entities_inside: Vec<RigidBodyId>,
@@ -43,6 +56,7 @@ pub struct Trigger {
 
 impl Trigger {
     pub fn insert(self, ctx: &ReducerContext) -> Self {
+        WorldCache::invalidate(self.world_id);
         ctx.db.physics_triggers().insert(self)
     }
 
@@ -51,15 +65,19 @@ impl Trigger {
     }
 
     pub fn update(self, ctx: &ReducerContext) -> Self {
+        WorldCache::invalidate(self.world_id);
         ctx.db.physics_triggers().id().update(self)
     }
 
     pub fn delete(self, ctx: &ReducerContext) {
+        WorldCache::invalidate(self.world_id);
         ctx.db.physics_triggers().id().delete(self.id);
     }
 
     pub fn delete_by_id(ctx: &ReducerContext, id: u64) {
-        ctx.db.physics_triggers().id().delete(id);
+        if let Some(trigger) = Self::find(ctx, id) {
+            trigger.delete(ctx);
+        }
     }
 
     pub fn all(ctx: &ReducerContext, world_id: u64) -> impl Iterator<Item = Self> {
@@ -2,6 +2,7 @@ use crate::RigidBodyData;
 
 /// Retrieves mutable references to two `RigidBody` instances by their IDs from a slice of bodies.
 /// This function assumes that the bodies are sorted by their IDs and that the IDs are unique.
+/// The bodies are returned in the same order as the requested IDs.
 pub fn get_bodies_mut(
     id_a: u64,
     id_b: u64,
@@ -25,7 +26,12 @@ pub fn get_bodies_mut(
         .map(|i| &mut left[i])
         .expect("ID not found");
     let b = &mut right[0];
-    (a, b)
+
+    if id_a < id_b {
+        (a, b)
+    } else {
+        (b, a)
+    }
 }
 
 pub fn get_bodies_direct(
@@ -41,3 +47,36 @@ pub fn get_bodies_direct(
 
     (a, b)
 }
+
+/// Retrieves a mutable reference to a `RigidBody` by its ID from a slice of bodies.
+/// This function assumes that the bodies are sorted by their IDs.
+pub fn get_body_mut(id: u64, bodies: &mut [RigidBodyData]) -> &mut RigidBodyData {
+    let index = bodies
+        .binary_search_by_key(&id, |b| b.id)
+        .expect("ID not found");
+    &mut bodies[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{math::Vec3, utils::sphere_body};
+
+    #[test]
+    fn get_bodies_mut_returns_the_bodies_in_requested_order() {
+        let mut bodies: Vec<_> = (1..=4).map(|id| sphere_body(id, Vec3::ZERO)).collect();
+
+        let (a, b) = get_bodies_mut(1, 3, &mut bodies);
+        assert_eq!((a.id, b.id), (1, 3));
+
+        let (a, b) = get_bodies_mut(4, 2, &mut bodies);
+        assert_eq!((a.id, b.id), (4, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "ID not found")]
+    fn get_bodies_mut_panics_on_missing_id() {
+        let mut bodies: Vec<_> = (1..=2).map(|id| sphere_body(id, Vec3::ZERO)).collect();
+        get_bodies_mut(1, 5, &mut bodies);
+    }
+}
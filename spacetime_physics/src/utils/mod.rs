@@ -1,5 +1,9 @@
 mod get_bodies;
 mod log_stopwatch;
+#[cfg(test)]
+mod test_bodies;
 
 pub use get_bodies::*;
 pub use log_stopwatch::*;
+#[cfg(test)]
+pub(crate) use test_bodies::*;
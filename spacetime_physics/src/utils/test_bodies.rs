@@ -0,0 +1,20 @@
+use std::collections::HashMap;
+
+use crate::{math::Vec3, Collider, RigidBody, RigidBodyData, RigidBodyProperties};
+
+/// Builds a dynamic unit sphere body of mass 1.0 without going through the database.
+pub(crate) fn sphere_body(id: u64, position: Vec3) -> RigidBodyData {
+    let collider = Collider::sphere(1, 0.5);
+    let rigid_body = RigidBody::builder()
+        .id(id)
+        .position(position)
+        .collider_id(collider.id)
+        .properties_id(0)
+        .build();
+    RigidBodyData::new(
+        rigid_body,
+        &RigidBodyProperties::builder().build(),
+        &collider,
+        &HashMap::new(),
+    )
+}